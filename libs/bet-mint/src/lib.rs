@@ -0,0 +1,40 @@
+//! Decides which mints `betting_contract` and `crypto_meme_wars` will hold
+//! bets in.
+//!
+//! Pools are credited the nominal amount of every transfer, so a mint must
+//! move exactly that amount and nobody but the escrow may move it out again.
+//! Token-2022 extensions that break either rule are rejected, even when they
+//! are dormant: a 0 bps transfer fee can be raised by its authority later.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    state::Mint,
+};
+
+pub const UNSUPPORTED_EXTENSIONS: [ExtensionType; 3] = [
+    ExtensionType::TransferFeeConfig, // escrow receives less than it credits
+    ExtensionType::PermanentDelegate, // delegate can move escrowed tokens
+    ExtensionType::TransferHook,      // hook program can fail or alter payouts
+];
+
+// Whether wars may be denominated in `mint`. Classic SPL Token mints have no
+// extensions; a Token-2022 mint that can't be parsed is refused.
+pub fn is_supported(mint: &AccountInfo) -> bool {
+    if *mint.owner != spl_token_2022::ID {
+        return true;
+    }
+
+    let Ok(data) = mint.try_borrow_data() else {
+        return false;
+    };
+    let Ok(extensions) =
+        StateWithExtensions::<Mint>::unpack(&data).and_then(|state| state.get_extension_types())
+    else {
+        return false;
+    };
+    !extensions
+        .iter()
+        .any(|extension| UNSUPPORTED_EXTENSIONS.contains(extension))
+}
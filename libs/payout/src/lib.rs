@@ -0,0 +1,176 @@
+//! Fee, payout and price-change math shared by `betting_contract` and
+//! `crypto_meme_wars`.
+//!
+//! Every function widens to u128/i128 for intermediates and returns
+//! `PayoutError::MathOverflow` rather than panicking. Rounding is always
+//! explicit:
+//! - fees and pool shares round down, so the pool never pays out more than
+//!   it holds and dust stays in escrow;
+//! - price changes round toward negative infinity, so any fall, however
+//!   small, ranks below an unchanged price.
+
+use anchor_lang::prelude::*;
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// Offset keeps these codes clear of each program's own 6000-range errors
+#[error_code(offset = 7000)]
+pub enum PayoutError {
+    #[msg("Arithmetic overflow in payout math")]
+    MathOverflow,
+}
+
+pub fn add(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b).ok_or_else(|| error!(PayoutError::MathOverflow))
+}
+
+pub fn sub(a: u64, b: u64) -> Result<u64> {
+    a.checked_sub(b).ok_or_else(|| error!(PayoutError::MathOverflow))
+}
+
+// floor(a * b / denominator); a zero denominator counts as overflow
+pub fn mul_div_floor(a: u64, b: u64, denominator: u64) -> Result<u64> {
+    let quotient = (a as u128 * b as u128)
+        .checked_div(denominator as u128)
+        .ok_or_else(|| error!(PayoutError::MathOverflow))?;
+    u64::try_from(quotient).map_err(|_| error!(PayoutError::MathOverflow))
+}
+
+// Fee charged on `amount` at `fee_bps`, rounded down in the bettor's favor
+pub fn fee(amount: u64, fee_bps: u16) -> Result<u64> {
+    require!(fee_bps as u64 <= BPS_DENOMINATOR, PayoutError::MathOverflow);
+    mul_div_floor(amount, fee_bps as u64, BPS_DENOMINATOR)
+}
+
+// $WAR holding tiers (6 decimals) and the fee discount each one earns, best first
+const WAR_DISCOUNT_TIERS: [(u64, u16); 4] = [
+    (1_000_000_000_000, 5_000), // 1M+ WAR: 50%
+    (100_000_000_000, 3_000),   // 100K+ WAR: 30%
+    (10_000_000_000, 1_500),    // 10K+ WAR: 15%
+    (1_000_000_000, 500),       // 1K+ WAR: 5%
+];
+
+// Fee discount earned by wallet plus staked $WAR holdings, in basis points
+pub fn war_discount_bps(total_war_holdings: u64) -> u16 {
+    WAR_DISCOUNT_TIERS
+        .iter()
+        .find(|&&(threshold, _)| total_war_holdings >= threshold)
+        .map_or(0, |&(_, discount_bps)| discount_bps)
+}
+
+// `base_fee` after a `discount_bps` discount, rounded down in the bettor's favor
+pub fn discounted_fee(base_fee: u64, discount_bps: u16) -> Result<u64> {
+    require!(discount_bps as u64 <= BPS_DENOMINATOR, PayoutError::MathOverflow);
+    mul_div_floor(base_fee, BPS_DENOMINATOR - discount_bps as u64, BPS_DENOMINATOR)
+}
+
+// Parimutuel share of `pool` owed to `stake` out of `winning_pool`, rounded down
+pub fn pro_rata_share(stake: u64, pool: u64, winning_pool: u64) -> Result<u64> {
+    mul_div_floor(stake, pool, winning_pool)
+}
+
+// What a bet of `stake` on pool `side` would return if the war settled now with `side` winning
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BetQuote {
+    pub stake: u64, // amount added to the pool, after any fee taken at bet time
+    pub projected_payout: u64,
+    pub odds_bps: u64, // projected_payout per unit staked, in basis points
+}
+
+// Quote a bet of `stake` on `pools[side]`, counting the stake in the pools. `extra_pool` joins
+// the prize without backing any side; `claim_fee_bps` is taken from the whole pool at claim.
+pub fn quote(
+    pools: &[u64],
+    side: usize,
+    stake: u64,
+    extra_pool: u64,
+    claim_fee_bps: u16,
+) -> Result<BetQuote> {
+    let winning_pool = add(pools[side], stake)?;
+    let total_pool = pools
+        .iter()
+        .try_fold(add(extra_pool, stake)?, |total, &pool| add(total, pool))?;
+    let prize_pool = sub(total_pool, fee(total_pool, claim_fee_bps)?)?;
+    let projected_payout = pro_rata_share(stake, prize_pool, winning_pool)?;
+    Ok(BetQuote {
+        stake,
+        projected_payout,
+        odds_bps: mul_div_floor(projected_payout, BPS_DENOMINATOR, stake)?,
+    })
+}
+
+// Signed change from `start_price` to `end_price` in basis points, floored
+pub fn price_change_bps(start_price: u64, end_price: u64) -> Result<i64> {
+    require!(start_price > 0, PayoutError::MathOverflow);
+    let delta = end_price as i128 - start_price as i128;
+    let change = (delta * BPS_DENOMINATOR as i128).div_euclid(start_price as i128);
+    i64::try_from(change).map_err(|_| error!(PayoutError::MathOverflow))
+}
+
+// Outcome of comparing two tokens' performance over a war
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settlement {
+    pub winner: Option<u8>,
+    pub token_a_change_bps: i64,
+    pub token_b_change_bps: i64,
+}
+
+// Compare both tokens by signed change, so the smaller loser wins a falling war
+pub fn settle(
+    token_a_start_price: u64,
+    token_a_end_price: u64,
+    token_b_start_price: u64,
+    token_b_end_price: u64,
+) -> Result<Settlement> {
+    let token_a_change_bps = price_change_bps(token_a_start_price, token_a_end_price)?;
+    let token_b_change_bps = price_change_bps(token_b_start_price, token_b_end_price)?;
+    Ok(Settlement {
+        winner: pick_winner(token_a_change_bps, token_b_change_bps),
+        token_a_change_bps,
+        token_b_change_bps,
+    })
+}
+
+// Larger change wins; equal changes are a tie (None)
+pub fn pick_winner(token_a_change_bps: i64, token_b_change_bps: i64) -> Option<u8> {
+    if token_a_change_bps > token_b_change_bps {
+        Some(0)
+    } else if token_b_change_bps > token_a_change_bps {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+// Outcome of comparing any number of contestants over a war
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSettlement {
+    pub winner: Option<u8>,
+    pub change_bps: Vec<i64>,
+}
+
+// Compare every contestant's `(start_price, end_price)` by signed change
+pub fn settle_field(prices: &[(u64, u64)]) -> Result<FieldSettlement> {
+    let change_bps = prices
+        .iter()
+        .map(|&(start_price, end_price)| price_change_bps(start_price, end_price))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(FieldSettlement {
+        winner: pick_best(&change_bps),
+        change_bps,
+    })
+}
+
+// Index of the largest change; a shared top change is a tie (None)
+pub fn pick_best(change_bps: &[i64]) -> Option<u8> {
+    let best = *change_bps.iter().max()?;
+    let mut leaders = change_bps
+        .iter()
+        .enumerate()
+        .filter(|(_, &change)| change == best);
+    let (winner, _) = leaders.next()?;
+    if leaders.next().is_some() {
+        return None;
+    }
+    u8::try_from(winner).ok()
+}
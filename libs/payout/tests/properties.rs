@@ -0,0 +1,127 @@
+use anchor_lang::error::Error;
+use payout::{
+    add, discounted_fee, fee, mul_div_floor, pick_winner, price_change_bps, pro_rata_share, sub,
+    war_discount_bps, PayoutError, BPS_DENOMINATOR,
+};
+use proptest::prelude::*;
+
+fn overflow() -> Error {
+    PayoutError::MathOverflow.into()
+}
+
+proptest! {
+    #[test]
+    fn add_and_sub_match_checked_ops(a: u64, b: u64) {
+        prop_assert_eq!(add(a, b).ok(), a.checked_add(b));
+        prop_assert_eq!(sub(a, b).ok(), a.checked_sub(b));
+    }
+
+    #[test]
+    fn mul_div_floor_matches_wide_reference(a: u64, b: u64, denominator in 1..=u64::MAX) {
+        let exact = a as u128 * b as u128 / denominator as u128;
+        match mul_div_floor(a, b, denominator) {
+            Ok(result) => prop_assert_eq!(result as u128, exact),
+            Err(error) => {
+                prop_assert!(exact > u64::MAX as u128);
+                prop_assert_eq!(error, overflow());
+            }
+        }
+    }
+
+    #[test]
+    fn fee_never_exceeds_amount(amount: u64, fee_bps in 0..=BPS_DENOMINATOR as u16) {
+        let charged = fee(amount, fee_bps).unwrap();
+        prop_assert!(charged <= amount);
+        // Rounded down: within one lamport of the exact fee
+        let exact_times_denominator = amount as u128 * fee_bps as u128;
+        prop_assert!(charged as u128 * BPS_DENOMINATOR as u128 <= exact_times_denominator);
+        prop_assert!((charged as u128 + 1) * BPS_DENOMINATOR as u128 > exact_times_denominator);
+    }
+
+    #[test]
+    fn fee_above_full_amount_is_rejected(amount: u64, fee_bps in (BPS_DENOMINATOR as u16 + 1)..) {
+        prop_assert_eq!(fee(amount, fee_bps), Err(overflow()));
+    }
+
+    #[test]
+    fn discounts_never_raise_the_fee(base_fee: u64, holdings: u64, more: u64) {
+        let discount_bps = war_discount_bps(holdings);
+        prop_assert!(discounted_fee(base_fee, discount_bps).unwrap() <= base_fee);
+        // Holding more $WAR never earns a smaller discount
+        prop_assert!(war_discount_bps(holdings.saturating_add(more)) >= discount_bps);
+    }
+
+    #[test]
+    fn winners_never_share_more_than_the_pool(
+        stakes in prop::collection::vec(1..=u64::MAX / 64, 1..32),
+        losing_pool in 0..=u64::MAX / 2,
+    ) {
+        let winning_pool: u64 = stakes.iter().sum();
+        let pool = winning_pool.saturating_add(losing_pool);
+        let mut paid: u128 = 0;
+        for stake in &stakes {
+            let share = pro_rata_share(*stake, pool, winning_pool).unwrap();
+            prop_assert!(share >= *stake);
+            paid += share as u128;
+        }
+        prop_assert!(paid <= pool as u128);
+        // Flooring leaves less than one lamport of dust per winner
+        prop_assert!(pool as u128 - paid < stakes.len() as u128);
+    }
+
+    #[test]
+    fn price_change_sign_follows_price(start in 1..=u64::MAX, end: u64) {
+        let change = price_change_bps(start, end);
+        let exact = (end as i128 - start as i128) * BPS_DENOMINATOR as i128;
+        match change {
+            Ok(bps) => {
+                prop_assert_eq!(bps as i128, exact.div_euclid(start as i128));
+                prop_assert_eq!(bps.signum() as i128, exact.signum());
+                prop_assert!(bps >= -(BPS_DENOMINATOR as i64));
+            }
+            Err(error) => {
+                prop_assert!(exact.div_euclid(start as i128) > i64::MAX as i128);
+                prop_assert_eq!(error, overflow());
+            }
+        }
+    }
+
+    #[test]
+    fn pick_winner_is_antisymmetric(a: i64, b: i64) {
+        let flipped = pick_winner(b, a).map(|winner| 1 - winner);
+        prop_assert_eq!(pick_winner(a, b), flipped);
+    }
+}
+
+#[test]
+fn zero_divisors_are_rejected() {
+    assert_eq!(mul_div_floor(1, 1, 0), Err(overflow()));
+    assert_eq!(pro_rata_share(1, 1, 0), Err(overflow()));
+    assert_eq!(price_change_bps(0, 1), Err(overflow()));
+}
+
+#[test]
+fn large_pools_do_not_overflow() {
+    let pool = u64::MAX / 2;
+    assert_eq!(pro_rata_share(pool, pool, pool).unwrap(), pool);
+    assert_eq!(fee(u64::MAX, 300).unwrap(), (u64::MAX as u128 * 300 / 10_000) as u64);
+}
+
+#[test]
+fn small_falls_rank_below_flat() {
+    assert_eq!(price_change_bps(1_000_000, 999_999).unwrap(), -1);
+    assert_eq!(price_change_bps(1_000_000, 1_000_000).unwrap(), 0);
+    assert_eq!(pick_winner(-1, 0), Some(1));
+}
+
+#[test]
+fn war_discount_tiers() {
+    const WAR: u64 = 1_000_000;
+    assert_eq!(war_discount_bps(999 * WAR), 0);
+    assert_eq!(war_discount_bps(1_000 * WAR), 500);
+    assert_eq!(war_discount_bps(10_000 * WAR), 1_500);
+    assert_eq!(war_discount_bps(100_000 * WAR), 3_000);
+    assert_eq!(war_discount_bps(1_000_000 * WAR), 5_000);
+    assert_eq!(discounted_fee(300, 500).unwrap(), 285);
+    assert_eq!(discounted_fee(301, 5_000).unwrap(), 150);
+}
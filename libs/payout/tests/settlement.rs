@@ -0,0 +1,97 @@
+use payout::{pick_best, quote, settle, settle_field, BetQuote, FieldSettlement, Settlement};
+
+const START: u64 = 1_000_000;
+
+#[test]
+fn up_up_bigger_gain_wins() {
+    assert_eq!(
+        settle(START, 1_200_000, START, 1_050_000).unwrap(),
+        Settlement {
+            winner: Some(0),
+            token_a_change_bps: 2_000,
+            token_b_change_bps: 500,
+        }
+    );
+}
+
+#[test]
+fn up_down_riser_wins() {
+    assert_eq!(
+        settle(START, 900_000, START, 1_010_000).unwrap(),
+        Settlement {
+            winner: Some(1),
+            token_a_change_bps: -1_000,
+            token_b_change_bps: 100,
+        }
+    );
+}
+
+#[test]
+fn down_down_least_bad_loser_wins() {
+    assert_eq!(
+        settle(START, 700_000, START, 400_000).unwrap(),
+        Settlement {
+            winner: Some(0),
+            token_a_change_bps: -3_000,
+            token_b_change_bps: -6_000,
+        }
+    );
+}
+
+#[test]
+fn total_wipeout_settles() {
+    assert_eq!(
+        settle(START, 0, START, 1_000).unwrap(),
+        Settlement {
+            winner: Some(1),
+            token_a_change_bps: -10_000,
+            token_b_change_bps: -9_990,
+        }
+    );
+}
+
+#[test]
+fn equal_moves_tie() {
+    assert_eq!(settle(START, 800_000, 2 * START, 1_600_000).unwrap().winner, None);
+    assert_eq!(settle(START, START, 3, 3).unwrap().winner, None);
+}
+
+#[test]
+fn field_best_performer_wins() {
+    let prices = [(START, 1_100_000), (START, 1_300_000), (START, 900_000), (START, 1_250_000)];
+    assert_eq!(
+        settle_field(&prices).unwrap(),
+        FieldSettlement {
+            winner: Some(1),
+            change_bps: vec![1_000, 3_000, -1_000, 2_500],
+        }
+    );
+}
+
+#[test]
+fn field_shared_top_ties() {
+    assert_eq!(pick_best(&[500, -200, 500]), None);
+    assert_eq!(pick_best(&[-700, -300, -300, -900]), None);
+    assert_eq!(pick_best(&[-700, -300, -400]), Some(1));
+    assert_eq!(pick_best(&[]), None);
+}
+
+#[test]
+fn field_rejects_zero_start_price() {
+    assert!(settle_field(&[(START, START), (0, START)]).is_err());
+}
+
+#[test]
+fn quote_counts_the_new_stake() {
+    // 1 more on the 1 side of a 1 vs 3 pool: 2 of 5 back it, less a 3% claim fee
+    assert_eq!(
+        quote(&[1_000_000, 3_000_000], 0, 1_000_000, 0, 300).unwrap(),
+        BetQuote {
+            stake: 1_000_000,
+            projected_payout: 2_425_000,
+            odds_bps: 24_250,
+        }
+    );
+    // Penalties in the pool sweeten the odds without backing a side
+    assert_eq!(quote(&[0, 0, 500], 2, 500, 1_000, 0).unwrap().odds_bps, 20_000);
+}
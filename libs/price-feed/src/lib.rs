@@ -0,0 +1,120 @@
+//! Reads prices from Pyth pull-oracle `PriceUpdateV2` accounts for
+//! `betting_contract` and `crypto_meme_wars`.
+//!
+//! Failures come back as a plain `PriceError`, which each program maps onto
+//! its own error codes so clients keep seeing that program's errors.
+
+use anchor_lang::prelude::*;
+
+// Pyth pull-oracle receiver; owns every `PriceUpdateV2` account we accept
+pub const PRICE_FEED_OWNER: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+pub const MAX_PRICE_AGE: i64 = 60; // seconds between publish and read
+pub const MAX_SNAPSHOT_DELAY: i64 = 300; // seconds a snapshot may trail start/end time
+pub const MAX_CONFIDENCE_BPS: u64 = 200; // 2% of price
+
+const PRICE_UPDATE_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceError {
+    InvalidPriceFeed,       // wrong owner, layout or verification level
+    FeedIdMismatch,         // update is for a different feed than the one registered
+    ExponentChanged,        // end price is not in the start price's exponent
+    InvalidPrice,           // non-positive price
+    StalePrice,             // published too long before the read
+    PriceOutsideWindow,     // not published within MAX_SNAPSHOT_DELAY of the target time
+    PriceConfidenceTooWide, // confidence interval above MAX_CONFIDENCE_BPS
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum VerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PriceFeedMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+// A checked price and the exponent it is quoted in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceReading {
+    pub price: u64,
+    pub exponent: i32,
+}
+
+// Layout of the receiver's `PriceUpdateV2` account, after the discriminator
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PriceUpdateV2 {
+    pub write_authority: Pubkey,
+    pub verification_level: VerificationLevel,
+    pub price_message: PriceFeedMessage,
+    pub posted_slot: u64,
+}
+
+impl PriceUpdateV2 {
+    pub const DISCRIMINATOR: [u8; 8] = PRICE_UPDATE_DISCRIMINATOR;
+
+    pub fn try_from_account(feed: &AccountInfo) -> std::result::Result<Self, PriceError> {
+        if *feed.owner != PRICE_FEED_OWNER {
+            return Err(PriceError::InvalidPriceFeed);
+        }
+
+        let data = feed.try_borrow_data().map_err(|_| PriceError::InvalidPriceFeed)?;
+        if data.len() < 8 || data[..8] != Self::DISCRIMINATOR {
+            return Err(PriceError::InvalidPriceFeed);
+        }
+        Self::deserialize(&mut &data[8..]).map_err(|_| PriceError::InvalidPriceFeed)
+    }
+}
+
+// Read a fully verified, fresh and tight price for `feed_id` published within
+// `MAX_SNAPSHOT_DELAY` of `target_time`. The update account's write authority
+// can repost it with another feed's message, so the feed id is always checked;
+// an end price must also keep the start price's exponent to be comparable.
+pub fn read_price(
+    feed: &AccountInfo,
+    feed_id: &[u8; 32],
+    expected_exponent: Option<i32>,
+    target_time: i64,
+    now: i64,
+) -> std::result::Result<PriceReading, PriceError> {
+    let update = PriceUpdateV2::try_from_account(feed)?;
+    if update.verification_level != VerificationLevel::Full {
+        return Err(PriceError::InvalidPriceFeed);
+    }
+
+    let message = &update.price_message;
+    if message.feed_id != *feed_id {
+        return Err(PriceError::FeedIdMismatch);
+    }
+    if expected_exponent.is_some_and(|exponent| exponent != message.exponent) {
+        return Err(PriceError::ExponentChanged);
+    }
+    if message.price <= 0 {
+        return Err(PriceError::InvalidPrice);
+    }
+    if message.publish_time > now || now - message.publish_time > MAX_PRICE_AGE {
+        return Err(PriceError::StalePrice);
+    }
+    if message.publish_time < target_time || message.publish_time - target_time > MAX_SNAPSHOT_DELAY
+    {
+        return Err(PriceError::PriceOutsideWindow);
+    }
+    if (message.conf as u128) * 10000 > (message.price as u128) * MAX_CONFIDENCE_BPS as u128 {
+        return Err(PriceError::PriceConfidenceTooWide);
+    }
+
+    Ok(PriceReading {
+        price: message.price as u64,
+        exponent: message.exponent,
+    })
+}
@@ -0,0 +1,478 @@
+//! Off-chain harness that drives a program's `entry` with locally crafted
+//! accounts, shared by the test suites of every program in this repo. The
+//! syscall stubs serve a per-thread clock, the default rent, return data,
+//! emitted events and the system, token and associated-token CPIs the programs make (create,
+//! transfer, transfer_checked and ATA creation).
+
+use std::cell::{Cell, RefCell};
+use std::sync::Once;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::program_stubs::{self, SyscallStubs};
+use anchor_lang::solana_program::program_utils::limited_deserialize;
+use anchor_lang::solana_program::system_instruction::SystemInstruction;
+use anchor_lang::{system_program, InstructionData};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+use anchor_spl::token::spl_token;
+use anchor_spl::token::spl_token::instruction::TokenInstruction;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    mint_close_authority::MintCloseAuthority, permanent_delegate::PermanentDelegate,
+    transfer_fee::TransferFeeConfig, transfer_hook::TransferHook, BaseStateWithExtensionsMut,
+    ExtensionType, StateWithExtensionsMut,
+};
+use price_feed::{PriceFeedMessage, PriceUpdateV2, VerificationLevel, PRICE_FEED_OWNER};
+
+/// A program's entrypoint, as generated by `#[program]`.
+pub type Entry = for<'info> fn(&Pubkey, &'info [AccountInfo<'info>], &[u8]) -> ProgramResult;
+
+pub fn ata_address(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(wallet, mint, &spl_token::ID)
+}
+
+/// Bytes of a token account of a mint carrying `mint_extensions`, as Anchor
+/// sizes it when initializing one.
+pub fn token_account_space(mint_extensions: &[ExtensionType]) -> usize {
+    let required = ExtensionType::get_required_init_account_extensions(mint_extensions);
+    ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&required).unwrap()
+}
+
+/// The message `TestAccount::price_feed` posts, for tests that alter one field.
+pub fn price_message(key: Pubkey, price: u64, conf: u64, publish_time: i64) -> PriceFeedMessage {
+    PriceFeedMessage {
+        feed_id: key.to_bytes(),
+        price: price as i64,
+        conf,
+        exponent: -8,
+        publish_time,
+        prev_publish_time: publish_time - 1,
+        ema_price: price as i64,
+        ema_conf: conf,
+    }
+}
+
+thread_local! {
+    static UNIX_TIMESTAMP: Cell<i64> = const { Cell::new(0) };
+    static RETURN_DATA: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+    static EVENTS: RefCell<Vec<Vec<u8>>> = const { RefCell::new(Vec::new()) };
+}
+
+struct TestStubs;
+
+impl SyscallStubs for TestStubs {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock {
+            unix_timestamp: UNIX_TIMESTAMP.with(Cell::get),
+            ..Clock::default()
+        };
+        unsafe { std::ptr::write_unaligned(var_addr as *mut Clock, clock) };
+        0
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { std::ptr::write_unaligned(var_addr as *mut Rent, Rent::default()) };
+        0
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        RETURN_DATA.with(|return_data| *return_data.borrow_mut() = data.to_vec());
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        EVENTS.with(|events| events.borrow_mut().push(fields.concat()));
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let find = |index: usize| {
+            let key = instruction.accounts[index].pubkey;
+            account_infos
+                .iter()
+                .find(|info| *info.key == key)
+                .expect("CPI account not passed to the program")
+        };
+
+        if instruction.program_id == associated_token::ID {
+            // Create or CreateIdempotent: payer, ata, wallet, mint, system, token program
+            let (payer, ata, wallet, mint) = (find(0), find(1), find(2), find(3));
+            let lamports = Rent::default().minimum_balance(spl_token::state::Account::LEN);
+            move_lamports(payer, ata, lamports)?;
+            ata.assign(&spl_token::ID);
+            let account = spl_token::state::Account {
+                mint: *mint.key,
+                owner: *wallet.key,
+                state: spl_token::state::AccountState::Initialized,
+                ..Default::default()
+            };
+            account.pack_into_slice(&mut ata.try_borrow_mut_data()?);
+            return Ok(());
+        }
+
+        // Token-2022 runs its real processor, so mint extensions behave as on chain
+        if instruction.program_id == spl_token_2022::ID {
+            let accounts: Vec<AccountInfo> = (0..instruction.accounts.len())
+                .map(|i| find(i).clone())
+                .collect();
+            return spl_token_2022::processor::Processor::process(
+                &instruction.program_id,
+                &accounts,
+                &instruction.data,
+            );
+        }
+
+        if instruction.program_id == spl_token::ID {
+            return match TokenInstruction::unpack(&instruction.data)? {
+                TokenInstruction::Transfer { amount } => move_tokens(find(0), find(1), amount),
+                TokenInstruction::TransferChecked { amount, .. } => {
+                    move_tokens(find(0), find(2), amount)
+                }
+                TokenInstruction::InitializeAccount3 { owner } => {
+                    let account = spl_token::state::Account {
+                        mint: *find(1).key,
+                        owner,
+                        state: spl_token::state::AccountState::Initialized,
+                        ..Default::default()
+                    };
+                    account.pack_into_slice(&mut find(0).try_borrow_mut_data()?);
+                    Ok(())
+                }
+                other => panic!("unsupported token instruction {other:?}"),
+            };
+        }
+
+        assert_eq!(instruction.program_id, system_program::ID, "unexpected CPI");
+        match limited_deserialize(&instruction.data, 1232)
+            .map_err(|_| ProgramError::InvalidInstructionData)?
+        {
+            SystemInstruction::CreateAccount {
+                lamports, owner, ..
+            } => {
+                let (from, to) = (find(0), find(1));
+                move_lamports(from, to, lamports)?;
+                to.assign(&owner);
+                Ok(())
+            }
+            SystemInstruction::Transfer { lamports } => move_lamports(find(0), find(1), lamports),
+            other => panic!("unsupported system instruction {other:?}"),
+        }
+    }
+}
+
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    let mut from_lamports = from.try_borrow_mut_lamports()?;
+    **from_lamports = from_lamports
+        .checked_sub(lamports)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    **to.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+fn move_tokens(from: &AccountInfo, to: &AccountInfo, amount: u64) -> ProgramResult {
+    let mut source = spl_token::state::Account::unpack(&from.try_borrow_data()?)?;
+    source.amount = source
+        .amount
+        .checked_sub(amount)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    source.pack_into_slice(&mut from.try_borrow_mut_data()?);
+
+    let mut destination = spl_token::state::Account::unpack(&to.try_borrow_data()?)?;
+    destination.amount += amount;
+    destination.pack_into_slice(&mut to.try_borrow_mut_data()?);
+    Ok(())
+}
+
+// Installs the stubs once per test binary
+fn install_stubs() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        program_stubs::set_syscall_stubs(Box::new(TestStubs));
+    });
+}
+
+/// Installs the stubs and sets this thread's clock.
+pub fn set_clock(unix_timestamp: i64) {
+    install_stubs();
+    UNIX_TIMESTAMP.with(|now| now.set(unix_timestamp));
+}
+
+#[derive(Clone)]
+pub struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub executable: bool,
+}
+
+impl TestAccount {
+    /// A system-owned wallet that signs the transaction.
+    pub fn signer(lamports: u64) -> Self {
+        Self::system(Pubkey::new_unique(), lamports).signed()
+    }
+
+    /// A system-owned account at `key`, e.g. a PDA holding SOL.
+    pub fn system(key: Pubkey, lamports: u64) -> Self {
+        Self {
+            key,
+            owner: system_program::ID,
+            lamports,
+            data: Vec::new(),
+            is_signer: false,
+            is_writable: true,
+            executable: false,
+        }
+    }
+
+    /// An account the program has yet to create, pre-sized to `space` bytes.
+    pub fn uninitialized(key: Pubkey, space: usize) -> Self {
+        Self {
+            data: vec![0; space],
+            ..Self::system(key, 0)
+        }
+    }
+
+    /// An account owned by `T`'s program holding `state`, padded to `space` bytes.
+    pub fn program_owned<T: AccountSerialize + Owner>(
+        key: Pubkey,
+        state: &T,
+        space: usize,
+    ) -> Self {
+        let mut data = Vec::with_capacity(space);
+        state.try_serialize(&mut data).unwrap();
+        data.resize(space, 0);
+        Self {
+            key,
+            owner: T::owner(),
+            lamports: Rent::default().minimum_balance(space),
+            data,
+            is_signer: false,
+            is_writable: true,
+            executable: false,
+        }
+    }
+
+    /// An SPL token mint with `decimals`.
+    pub fn mint(key: Pubkey, decimals: u8) -> Self {
+        let mint = spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply: u64::MAX / 2,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        Self::token_owned(key, &mint)
+    }
+
+    /// A Token-2022 mint with `decimals` and each of `extensions` set to its
+    /// defaults, e.g. a transfer fee of 0 bps.
+    pub fn mint_2022(key: Pubkey, decimals: u8, extensions: &[ExtensionType]) -> Self {
+        let space =
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(extensions)
+                .unwrap();
+        let mut data = vec![0; space];
+        let mut state =
+            StateWithExtensionsMut::<spl_token_2022::state::Mint>::unpack_uninitialized(&mut data)
+                .unwrap();
+        for extension in extensions {
+            match extension {
+                ExtensionType::TransferFeeConfig => {
+                    state.init_extension::<TransferFeeConfig>(true).unwrap();
+                }
+                ExtensionType::PermanentDelegate => {
+                    state.init_extension::<PermanentDelegate>(true).unwrap();
+                }
+                ExtensionType::TransferHook => {
+                    state.init_extension::<TransferHook>(true).unwrap();
+                }
+                ExtensionType::MintCloseAuthority => {
+                    state.init_extension::<MintCloseAuthority>(true).unwrap();
+                }
+                other => panic!("unsupported mint extension {other:?}"),
+            }
+        }
+        state.base = spl_token_2022::state::Mint {
+            mint_authority: COption::None,
+            supply: u64::MAX / 2,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        Self {
+            key,
+            owner: spl_token_2022::ID,
+            lamports: Rent::default().minimum_balance(space),
+            data,
+            is_signer: false,
+            is_writable: true,
+            executable: false,
+        }
+    }
+
+    /// An SPL token account of `mint` held by `owner`.
+    pub fn token_account(key: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) -> Self {
+        let account = spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        Self::token_owned(key, &account)
+    }
+
+    fn token_owned<T: Pack>(key: Pubkey, state: &T) -> Self {
+        let mut data = vec![0; T::LEN];
+        state.pack_into_slice(&mut data);
+        Self {
+            key,
+            owner: spl_token::ID,
+            lamports: Rent::default().minimum_balance(T::LEN),
+            data,
+            is_signer: false,
+            is_writable: true,
+            executable: false,
+        }
+    }
+
+    /// A fully verified oracle price update for the feed id `key.to_bytes()`,
+    /// quoted with exponent -8.
+    pub fn price_feed(key: Pubkey, price: u64, conf: u64, publish_time: i64) -> Self {
+        Self::price_update(key, price_message(key, price, conf, publish_time))
+    }
+
+    /// A fully verified oracle price update at `key` carrying `message`.
+    pub fn price_update(key: Pubkey, message: PriceFeedMessage) -> Self {
+        let update = PriceUpdateV2 {
+            write_authority: Pubkey::new_unique(),
+            verification_level: VerificationLevel::Full,
+            price_message: message,
+            posted_slot: 0,
+        };
+        let mut data = PriceUpdateV2::DISCRIMINATOR.to_vec();
+        update.serialize(&mut data).unwrap();
+        Self {
+            key,
+            owner: PRICE_FEED_OWNER,
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            is_signer: false,
+            is_writable: false,
+            executable: false,
+        }
+    }
+
+    pub fn system_program() -> Self {
+        Self::executable(system_program::ID)
+    }
+
+    pub fn token_program() -> Self {
+        Self::executable(spl_token::ID)
+    }
+
+    pub fn token_2022_program() -> Self {
+        Self::executable(spl_token_2022::ID)
+    }
+
+    pub fn associated_token_program() -> Self {
+        Self::executable(associated_token::ID)
+    }
+
+    /// Stands in for an optional account of `program` that is left out.
+    pub fn none(program: Pubkey) -> Self {
+        Self::executable(program)
+    }
+
+    fn executable(key: Pubkey) -> Self {
+        Self {
+            key,
+            owner: anchor_lang::solana_program::bpf_loader::ID,
+            lamports: 1,
+            data: Vec::new(),
+            is_signer: false,
+            is_writable: false,
+            executable: true,
+        }
+    }
+
+    pub fn signed(mut self) -> Self {
+        self.is_signer = true;
+        self
+    }
+
+    pub fn state<T: AccountDeserialize>(&self) -> T {
+        T::try_deserialize(&mut self.data.as_slice()).unwrap()
+    }
+
+    pub fn token_amount(&self) -> u64 {
+        spl_token::state::Account::unpack(&self.data)
+            .unwrap()
+            .amount
+    }
+
+    fn info(&mut self) -> AccountInfo<'_> {
+        AccountInfo::new(
+            &self.key,
+            self.is_signer,
+            self.is_writable,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            self.executable,
+            0,
+        )
+    }
+}
+
+/// Runs one instruction through `program`'s entrypoint. Like the runtime, a failed
+/// instruction leaves the accounts as they were.
+pub fn process<I: InstructionData>(
+    entry: Entry,
+    program: &Pubkey,
+    ix: I,
+    accounts: &mut [TestAccount],
+) -> ProgramResult {
+    install_stubs();
+    let before = accounts.to_vec();
+    EVENTS.with(|events| events.borrow_mut().clear());
+    let result = {
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        entry(program, &infos, &ix.data())
+    };
+    if result.is_err() {
+        accounts.clone_from_slice(&before);
+    }
+    result
+}
+
+/// Decodes the return data set by the last instruction on this thread.
+pub fn return_data<T: AnchorDeserialize>() -> T {
+    RETURN_DATA.with(|return_data| T::try_from_slice(&return_data.borrow()).unwrap())
+}
+
+/// The `T` events emitted by the last instruction on this thread.
+pub fn events<T: anchor_lang::Event>() -> Vec<T> {
+    EVENTS.with(|events| {
+        events
+            .borrow()
+            .iter()
+            .filter_map(|data| data.strip_prefix(&T::DISCRIMINATOR[..]))
+            .map(|data| T::try_from_slice(data).unwrap())
+            .collect()
+    })
+}
+
+pub fn error_code<E: Into<u32>>(error: E) -> ProgramResult {
+    Err(ProgramError::Custom(error.into()))
+}
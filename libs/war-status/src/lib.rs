@@ -0,0 +1,50 @@
+//! The war lifecycle shared by `betting_contract` and `crypto_meme_wars`.
+//!
+//! Both programs move a war through the same statuses and allow the same
+//! transitions. Rejections come back as a plain `StatusError`, which each
+//! program maps onto its own error codes.
+
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WarStatus {
+    Open,   // taking bets until betting closes
+    Locked, // betting closed, awaiting a result
+    Settling { winner: Option<u8>, proposed_at: i64 }, // oracle result inside the dispute window
+    Disputed, // oracle result rejected; settled or cancelled by hand
+    Settled { winner: Option<u8> }, // winning index, None for a tie
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusError {
+    InvalidWarStatus,  // Open or Locked
+    SettlementPending, // Settling or Disputed
+    WarAlreadySettled,
+    WarCancelled,
+}
+
+impl WarStatus {
+    pub const SPACE: usize = 1 + 2 + 8; // largest variant: Settling
+
+    pub fn can_transition_to(&self, next: &WarStatus) -> bool {
+        use WarStatus::*;
+        matches!(
+            (self, next),
+            (Open, Locked | Cancelled)
+                | (Locked, Settling { .. } | Settled { .. } | Cancelled)
+                | (Settling { .. }, Settled { .. } | Disputed | Cancelled)
+                | (Disputed, Settled { .. } | Cancelled)
+        )
+    }
+
+    // Why an instruction that is not allowed in this status is rejected
+    pub fn error(&self) -> StatusError {
+        match self {
+            WarStatus::Open | WarStatus::Locked => StatusError::InvalidWarStatus,
+            WarStatus::Settling { .. } | WarStatus::Disputed => StatusError::SettlementPending,
+            WarStatus::Settled { .. } => StatusError::WarAlreadySettled,
+            WarStatus::Cancelled => StatusError::WarCancelled,
+        }
+    }
+}
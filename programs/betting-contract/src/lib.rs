@@ -1,28 +1,50 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+pub use war_status::{StatusError, WarStatus};
+
+pub mod oracle;
 
 declare_id!("7KK67M12SbodyTKSetMjMeCWBiDNvB817dkWWvueRbYG");
 
+pub const PLATFORM_FEE_BPS: u16 = 300; // 3%, taken from the pool at claim time
+pub const CRANK_BOUNTY_BPS: u64 = 500; // 5% of the war's platform fee per snapshot/settlement crank
+pub const SETTLEMENT_DEADLINE: i64 = 86_400; // seconds after end_time before anyone may cancel
+pub const DISPUTE_WINDOW: i64 = 3_600; // seconds the authority has to dispute an oracle settlement
+
 #[program]
 pub mod betting_contract {
     use super::*;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_war(
         ctx: Context<InitializeWar>,
         token_a: Pubkey,
         token_b: Pubkey,
         start_time: i64,
         end_time: i64,
+        token_a_price_feed: Pubkey,
+        token_b_price_feed: Pubkey,
+        token_a_feed_id: [u8; 32], // Pyth feed id each update on the feed account must carry
+        token_b_feed_id: [u8; 32],
     ) -> Result<()> {
         let war = &mut ctx.accounts.war;
         war.authority = ctx.accounts.authority.key();
         war.token_a = token_a;
         war.token_b = token_b;
+        war.token_a_price_feed = token_a_price_feed;
+        war.token_b_price_feed = token_b_price_feed;
+        war.token_a_feed_id = token_a_feed_id;
+        war.token_b_feed_id = token_b_feed_id;
         war.start_time = start_time;
         war.end_time = end_time;
         war.total_bets_a = 0;
         war.total_bets_b = 0;
-        war.is_settled = false;
-        war.winner = None;
+        war.status = WarStatus::Open;
+        war.vault_bump = ctx.bumps.vault;
+        war.bet_mint = ctx.accounts.bet_mint.key();
+
         Ok(())
     }
 
@@ -30,27 +52,33 @@ pub mod betting_contract {
         ctx: Context<PlaceBet>,
         amount: u64,
         token_choice: u8, // 0 for token_a, 1 for token_b
+        nonce: u64,
     ) -> Result<()> {
         let war = &mut ctx.accounts.war;
         let bet = &mut ctx.accounts.bet;
         
-        require!(!war.is_settled, BettingError::WarAlreadySettled);
+        require!(war.status == WarStatus::Open, war.status.rejection());
         require!(token_choice <= 1, BettingError::InvalidTokenChoice);
         
         let clock = Clock::get()?;
         require!(clock.unix_timestamp >= war.start_time, BettingError::WarNotStarted);
         require!(clock.unix_timestamp < war.end_time, BettingError::WarEnded);
 
-        // Transfer SOL from user to program
-        let transfer_instruction = anchor_lang::system_program::Transfer {
-            from: ctx.accounts.user.to_account_info(),
-            to: ctx.accounts.program_account.to_account_info(),
+        // Transfer the stake from the user to the war vault
+        let transfer_instruction = TransferChecked {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.bet_mint.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
         };
-        let cpi_ctx = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            transfer_instruction,
-        );
-        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_instruction,
+            ),
+            amount,
+            ctx.accounts.bet_mint.decimals,
+        )?;
 
         // Record bet details
         bet.user = ctx.accounts.user.key();
@@ -58,17 +86,40 @@ pub mod betting_contract {
         bet.amount = amount;
         bet.token_choice = token_choice;
         bet.timestamp = clock.unix_timestamp;
+        bet.nonce = nonce;
+        bet.is_claimed = false;
+        bet.bump = ctx.bumps.bet;
 
         // Update war totals
         if token_choice == 0 {
-            war.total_bets_a = war.total_bets_a.checked_add(amount).unwrap();
+            war.total_bets_a = payout::add(war.total_bets_a, amount)?;
         } else {
-            war.total_bets_b = war.total_bets_b.checked_add(amount).unwrap();
+            war.total_bets_b = payout::add(war.total_bets_b, amount)?;
         }
 
         Ok(())
     }
 
+    // Read-only: quote a bet of `amount` on `token_choice` against the current pools,
+    // returned as Anchor return data for clients to simulate
+    pub fn quote_bet(
+        ctx: Context<QuoteBet>,
+        amount: u64,
+        token_choice: u8,
+    ) -> Result<payout::BetQuote> {
+        let war = &ctx.accounts.war;
+        require!(amount > 0, BettingError::InvalidAmount);
+        require!(token_choice <= 1, BettingError::InvalidTokenChoice);
+
+        payout::quote(
+            &[war.total_bets_a, war.total_bets_b],
+            token_choice as usize,
+            amount,
+            0,
+            PLATFORM_FEE_BPS,
+        )
+    }
+
     pub fn settle_war(
         ctx: Context<SettleWar>,
         token_a_price_change: i64,
@@ -76,33 +127,199 @@ pub mod betting_contract {
     ) -> Result<()> {
         let war = &mut ctx.accounts.war;
         
-        require!(!war.is_settled, BettingError::WarAlreadySettled);
         require!(ctx.accounts.authority.key() == war.authority, BettingError::Unauthorized);
         
         let clock = Clock::get()?;
-        require!(clock.unix_timestamp >= war.end_time, BettingError::WarNotEnded);
+        war.close_betting(clock.unix_timestamp)?;
 
-        // Determine winner based on price changes
-        war.winner = if token_a_price_change > token_b_price_change {
-            Some(0) // Token A wins
-        } else if token_b_price_change > token_a_price_change {
-            Some(1) // Token B wins
-        } else {
-            None // Tie - refund all bets
+        // Determine winner based on price changes; also resolves a disputed oracle result
+        let winner = payout::pick_winner(token_a_price_change, token_b_price_change);
+        war.transition(WarStatus::Settled { winner })
+    }
+
+    // Snapshot both registered feeds once the war has started; callable by anyone.
+    // The snapshot bounty is paid at settlement so a cancelled war can refund in full.
+    pub fn snapshot_start_price(ctx: Context<SnapshotStartPrice>) -> Result<()> {
+        let war = &mut ctx.accounts.war;
+
+        require!(
+            matches!(war.status, WarStatus::Open | WarStatus::Locked),
+            war.status.rejection()
+        );
+        require!(war.token_a_start_price == 0, BettingError::StartPricesAlreadyRecorded);
+
+        let clock = Clock::get()?;
+        require!(clock.unix_timestamp >= war.start_time, BettingError::WarNotStarted);
+
+        let price_a = oracle::read_price(
+            &ctx.accounts.token_a_price_feed,
+            &war.token_a_feed_id,
+            None,
+            war.start_time,
+            clock.unix_timestamp,
+        )?;
+        let price_b = oracle::read_price(
+            &ctx.accounts.token_b_price_feed,
+            &war.token_b_feed_id,
+            None,
+            war.start_time,
+            clock.unix_timestamp,
+        )?;
+        war.token_a_start_price = price_a.price;
+        war.token_b_start_price = price_b.price;
+        war.token_a_exponent = price_a.exponent;
+        war.token_b_exponent = price_b.exponent;
+        war.snapshot_cranker = ctx.accounts.cranker.key();
+
+        Ok(())
+    }
+
+    // Propose a result from the registered feeds after end_time; callable by anyone.
+    // The result stands once DISPUTE_WINDOW passes without a dispute.
+    pub fn settle_war_permissionless(ctx: Context<SettleWarPermissionless>) -> Result<()> {
+        let war = &mut ctx.accounts.war;
+
+        let clock = Clock::get()?;
+        war.close_betting(clock.unix_timestamp)?;
+        require!(war.status == WarStatus::Locked, war.status.rejection());
+        require!(war.token_a_start_price > 0, BettingError::StartPricesNotRecorded);
+
+        // End prices must be quoted in the start prices' exponents to be compared
+        war.token_a_end_price = oracle::read_price(
+            &ctx.accounts.token_a_price_feed,
+            &war.token_a_feed_id,
+            Some(war.token_a_exponent),
+            war.end_time,
+            clock.unix_timestamp,
+        )?
+        .price;
+        war.token_b_end_price = oracle::read_price(
+            &ctx.accounts.token_b_price_feed,
+            &war.token_b_feed_id,
+            Some(war.token_b_exponent),
+            war.end_time,
+            clock.unix_timestamp,
+        )?
+        .price;
+
+        let winner = payout::settle(
+            war.token_a_start_price,
+            war.token_a_end_price,
+            war.token_b_start_price,
+            war.token_b_end_price,
+        )?
+        .winner;
+
+        war.settle_cranker = ctx.accounts.cranker.key();
+        war.transition(WarStatus::Settling {
+            winner,
+            proposed_at: clock.unix_timestamp,
+        })
+    }
+
+    // Flag an oracle result as wrong; the authority then settles or cancels by hand
+    pub fn dispute_settlement(ctx: Context<DisputeSettlement>) -> Result<()> {
+        let war = &mut ctx.accounts.war;
+
+        require!(ctx.accounts.authority.key() == war.authority, BettingError::Unauthorized);
+        if let WarStatus::Settling { proposed_at, .. } = war.status {
+            let clock = Clock::get()?;
+            require!(
+                clock.unix_timestamp < proposed_at + DISPUTE_WINDOW,
+                BettingError::DisputeWindowClosed
+            );
+        }
+
+        war.transition(WarStatus::Disputed)
+    }
+
+    // Finalize an undisputed oracle result and pay both cranks unless it is a tie; callable by anyone
+    pub fn finalize_settlement(ctx: Context<FinalizeSettlement>) -> Result<()> {
+        let war = &mut ctx.accounts.war;
+
+        let WarStatus::Settling { winner, proposed_at } = war.status else {
+            return err!(war.status.rejection());
         };
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp >= proposed_at + DISPUTE_WINDOW,
+            BettingError::DisputeWindowOpen
+        );
+
+        war.transition(WarStatus::Settled { winner })?;
+
+        // A tie refunds every stake in full, so there is no platform fee to pay cranks from
+        if winner.is_some() {
+            let crankers = [
+                &ctx.accounts.settle_cranker_token_account,
+                &ctx.accounts.snapshot_cranker_token_account,
+            ];
+            for cranker in crankers {
+                pay_crank_bounty(
+                    war,
+                    &ctx.accounts.vault,
+                    &ctx.accounts.bet_mint,
+                    cranker,
+                    &ctx.accounts.token_program,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Void the war: the authority may cancel any time before settlement, anyone
+    // once SETTLEMENT_DEADLINE has passed without one. A pending or disputed
+    // result can only be cancelled by the authority.
+    pub fn cancel_war(ctx: Context<CancelWar>) -> Result<()> {
+        let war = &mut ctx.accounts.war;
+
+        if ctx.accounts.caller.key() != war.authority {
+            let clock = Clock::get()?;
+            require!(
+                matches!(war.status, WarStatus::Open | WarStatus::Locked)
+                    && clock.unix_timestamp >= war.end_time + SETTLEMENT_DEADLINE,
+                BettingError::Unauthorized
+            );
+        }
+
+        war.transition(WarStatus::Cancelled)
+    }
+
+    // Return the full stake of a bet on a cancelled war
+    pub fn refund_bet(ctx: Context<ClaimWinnings>) -> Result<()> {
+        let war = &ctx.accounts.war;
+        let bet = &mut ctx.accounts.bet;
+
+        require!(war.status == WarStatus::Cancelled, BettingError::WarNotCancelled);
+        require!(!bet.is_claimed, BettingError::AlreadyClaimed);
+        require!(bet.user == ctx.accounts.user.key(), BettingError::Unauthorized);
+
+        transfer_from_vault(
+            war,
+            &ctx.accounts.vault,
+            &ctx.accounts.bet_mint,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
+            bet.amount,
+        )?;
+
+        bet.is_claimed = true;
 
-        war.is_settled = true;
         Ok(())
     }
 
     pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
         let war = &ctx.accounts.war;
-        let bet = &ctx.accounts.bet;
+        let bet = &mut ctx.accounts.bet;
         
-        require!(war.is_settled, BettingError::WarNotSettled);
+        let WarStatus::Settled { winner } = war.status else {
+            return err!(BettingError::WarNotSettled);
+        };
+        require!(!bet.is_claimed, BettingError::AlreadyClaimed);
         require!(bet.user == ctx.accounts.user.key(), BettingError::Unauthorized);
 
-        let payout = match war.winner {
+        let payout = match winner {
             Some(winning_token) if winning_token == bet.token_choice => {
                 // Calculate proportional payout
                 let total_winning_bets = if winning_token == 0 {
@@ -110,57 +327,151 @@ pub mod betting_contract {
                 } else {
                     war.total_bets_b
                 };
-                let total_pool = war.total_bets_a + war.total_bets_b;
-                let platform_fee = total_pool * 3 / 100; // 3% fee
-                let prize_pool = total_pool - platform_fee;
-                
-                (bet.amount * prize_pool) / total_winning_bets
+                let total_pool = payout::add(war.total_bets_a, war.total_bets_b)?;
+                let platform_fee = payout::fee(total_pool, PLATFORM_FEE_BPS)?;
+                let prize_pool = payout::sub(total_pool, platform_fee)?;
+
+                payout::pro_rata_share(bet.amount, prize_pool, total_winning_bets)?
             }
             None => bet.amount, // Tie - refund original bet
             _ => 0, // Lost bet
         };
 
         if payout > 0 {
-            // Transfer payout from program to user
-            **ctx.accounts.program_account.to_account_info().try_borrow_mut_lamports()? -= payout;
-            **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += payout;
+            transfer_from_vault(
+                war,
+                &ctx.accounts.vault,
+                &ctx.accounts.bet_mint,
+                &ctx.accounts.user_token_account,
+                &ctx.accounts.token_program,
+                payout,
+            )?;
         }
 
+        bet.is_claimed = true;
+
         Ok(())
     }
 }
 
+// Helper functions
+// Pay a cranker its share of the war's platform fee
+fn pay_crank_bounty<'info>(
+    war: &mut Account<'info, War>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    bet_mint: &InterfaceAccount<'info, Mint>,
+    cranker: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    let fee_pool = payout::fee(
+        payout::add(war.total_bets_a, war.total_bets_b)?,
+        PLATFORM_FEE_BPS,
+    )?;
+    let bounty = payout::mul_div_floor(fee_pool, CRANK_BOUNTY_BPS, payout::BPS_DENOMINATOR)?;
+    if bounty == 0 {
+        return Ok(());
+    }
+
+    transfer_from_vault(war, vault, bet_mint, cranker, token_program, bounty)?;
+
+    war.bounties_paid = payout::add(war.bounties_paid, bounty)?;
+    Ok(())
+}
+
+// Move `amount` out of the war vault, signed by the vault PDA
+fn transfer_from_vault<'info>(
+    war: &Account<'info, War>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    bet_mint: &InterfaceAccount<'info, Mint>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let war_key = war.key();
+    let seeds = &[b"vault", war_key.as_ref(), &[war.vault_bump]];
+    let signer = &[&seeds[..]];
+
+    let transfer_instruction = TransferChecked {
+        from: vault.to_account_info(),
+        mint: bet_mint.to_account_info(),
+        to: to.to_account_info(),
+        authority: vault.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            transfer_instruction,
+            signer,
+        ),
+        amount,
+        bet_mint.decimals,
+    )
+}
+
 #[derive(Accounts)]
 pub struct InitializeWar<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1 + 1
+        space = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + WarStatus::SPACE + 1 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 32 + 32 + 32 + 32 + 32 + 4 + 4
     )]
     pub war: Account<'info, War>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"vault", war.key().as_ref()],
+        bump,
+        token::mint = bet_mint,
+        token::authority = vault,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(constraint = bet_mint::is_supported(&bet_mint.to_account_info()) @ BettingError::UnsupportedMint)]
+    pub bet_mint: InterfaceAccount<'info, Mint>,
     #[account(mut)]
     pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
+#[instruction(amount: u64, token_choice: u8, nonce: u64)]
 pub struct PlaceBet<'info> {
     #[account(mut)]
     pub war: Account<'info, War>,
     #[account(
         init,
         payer = user,
-        space = 8 + 32 + 32 + 8 + 1 + 8
+        seeds = [b"bet", war.key().as_ref(), user.key().as_ref(), &nonce.to_le_bytes()],
+        bump,
+        space = 8 + 32 + 32 + 8 + 1 + 8 + 8 + 1 + 1
     )]
     pub bet: Account<'info, Bet>,
+    #[account(
+        mut,
+        seeds = [b"vault", war.key().as_ref()],
+        bump = war.vault_bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = war.bet_mint @ BettingError::InvalidMint)]
+    pub bet_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = bet_mint,
+        token::authority = user
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     #[account(mut)]
     pub user: Signer<'info>,
-    /// CHECK: Program account to receive SOL
-    #[account(mut)]
-    pub program_account: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct QuoteBet<'info> {
+    pub war: Account<'info, War>,
+}
+
 #[derive(Accounts)]
 pub struct SettleWar<'info> {
     #[account(mut)]
@@ -168,15 +479,113 @@ pub struct SettleWar<'info> {
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct SnapshotStartPrice<'info> {
+    #[account(mut)]
+    pub war: Account<'info, War>,
+    /// CHECK: Must be the registered feed; owner, layout and feed id checked in `oracle::read_price`
+    #[account(address = war.token_a_price_feed @ BettingError::InvalidPriceFeed)]
+    pub token_a_price_feed: AccountInfo<'info>,
+    /// CHECK: Must be the registered feed; owner, layout and feed id checked in `oracle::read_price`
+    #[account(address = war.token_b_price_feed @ BettingError::InvalidPriceFeed)]
+    pub token_b_price_feed: AccountInfo<'info>,
+    pub cranker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleWarPermissionless<'info> {
+    #[account(mut)]
+    pub war: Account<'info, War>,
+    /// CHECK: Must be the registered feed; owner, layout and feed id checked in `oracle::read_price`
+    #[account(address = war.token_a_price_feed @ BettingError::InvalidPriceFeed)]
+    pub token_a_price_feed: AccountInfo<'info>,
+    /// CHECK: Must be the registered feed; owner, layout and feed id checked in `oracle::read_price`
+    #[account(address = war.token_b_price_feed @ BettingError::InvalidPriceFeed)]
+    pub token_b_price_feed: AccountInfo<'info>,
+    pub cranker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeSettlement<'info> {
+    #[account(mut)]
+    pub war: Account<'info, War>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeSettlement<'info> {
+    #[account(mut)]
+    pub war: Account<'info, War>,
+    #[account(
+        mut,
+        seeds = [b"vault", war.key().as_ref()],
+        bump = war.vault_bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = war.bet_mint @ BettingError::InvalidMint)]
+    pub bet_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: Owns the settlement bounty's ATA; must match the recorded cranker
+    #[account(address = war.settle_cranker @ BettingError::Unauthorized)]
+    pub settle_cranker: UncheckedAccount<'info>,
+    // Created if the cranker closed or never opened it, so finalizing can't be blocked
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = bet_mint,
+        associated_token::authority = settle_cranker,
+        associated_token::token_program = token_program
+    )]
+    pub settle_cranker_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Owns the deferred snapshot bounty's ATA; must match the recorded cranker
+    #[account(address = war.snapshot_cranker @ BettingError::Unauthorized)]
+    pub snapshot_cranker: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = bet_mint,
+        associated_token::authority = snapshot_cranker,
+        associated_token::token_program = token_program
+    )]
+    pub snapshot_cranker_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelWar<'info> {
+    #[account(mut)]
+    pub war: Account<'info, War>,
+    pub caller: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct ClaimWinnings<'info> {
     pub war: Account<'info, War>,
+    #[account(
+        mut,
+        seeds = [b"bet", war.key().as_ref(), user.key().as_ref(), &bet.nonce.to_le_bytes()],
+        bump = bet.bump
+    )]
     pub bet: Account<'info, Bet>,
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"vault", war.key().as_ref()],
+        bump = war.vault_bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = war.bet_mint @ BettingError::InvalidMint)]
+    pub bet_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = bet_mint,
+        token::authority = user
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     pub user: Signer<'info>,
-    /// CHECK: Program account to send SOL from
-    #[account(mut)]
-    pub program_account: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[account]
@@ -188,8 +597,56 @@ pub struct War {
     pub end_time: i64,
     pub total_bets_a: u64,
     pub total_bets_b: u64,
-    pub is_settled: bool,
-    pub winner: Option<u8>, // 0 = token_a, 1 = token_b, None = tie
+    pub status: WarStatus,
+    pub vault_bump: u8,
+    pub token_a_price_feed: Pubkey,
+    pub token_b_price_feed: Pubkey,
+    pub token_a_start_price: u64, // 0 until recorded from the feed
+    pub token_b_start_price: u64,
+    pub token_a_end_price: u64,
+    pub token_b_end_price: u64,
+    pub bounties_paid: u64, // crank bounties taken out of the platform fee
+    pub snapshot_cranker: Pubkey, // paid its bounty when the war settles
+    pub settle_cranker: Pubkey,
+    pub bet_mint: Pubkey, // stakes, payouts and bounties are all in this mint
+    pub token_a_feed_id: [u8; 32], // Pyth feed id expected in each registered feed account
+    pub token_b_feed_id: [u8; 32],
+    pub token_a_exponent: i32, // set with the start price; end prices must match
+    pub token_b_exponent: i32,
+}
+
+impl War {
+    // Move to `next`, rejecting any transition the lifecycle does not allow
+    pub fn transition(&mut self, next: WarStatus) -> Result<()> {
+        require!(self.status.can_transition_to(&next), self.status.rejection());
+        self.status = next;
+        Ok(())
+    }
+
+    // Stop taking bets once end_time has passed
+    pub fn close_betting(&mut self, now: i64) -> Result<()> {
+        require!(now >= self.end_time, BettingError::WarNotEnded);
+        if self.status == WarStatus::Open {
+            self.transition(WarStatus::Locked)?;
+        }
+        Ok(())
+    }
+}
+
+// This program's error for an instruction not allowed in the war's status
+pub trait Rejection {
+    fn rejection(&self) -> BettingError;
+}
+
+impl Rejection for WarStatus {
+    fn rejection(&self) -> BettingError {
+        match self.error() {
+            StatusError::InvalidWarStatus => BettingError::InvalidWarStatus,
+            StatusError::SettlementPending => BettingError::SettlementPending,
+            StatusError::WarAlreadySettled => BettingError::WarAlreadySettled,
+            StatusError::WarCancelled => BettingError::WarCancelled,
+        }
+    }
 }
 
 #[account]
@@ -199,6 +656,9 @@ pub struct Bet {
     pub amount: u64,
     pub token_choice: u8,
     pub timestamp: i64,
+    pub nonce: u64,
+    pub is_claimed: bool,
+    pub bump: u8,
 }
 
 #[error_code]
@@ -217,4 +677,42 @@ pub enum BettingError {
     WarNotSettled,
     #[msg("Unauthorized")]
     Unauthorized,
+    #[msg("Winnings have already been claimed")]
+    AlreadyClaimed,
+    #[msg("Price feed account is not the registered oracle feed")]
+    InvalidPriceFeed,
+    #[msg("Price feed reported a non-positive price")]
+    InvalidPrice,
+    #[msg("Price feed is stale")]
+    StalePrice,
+    #[msg("Price was not published within the snapshot window")]
+    PriceOutsideWindow,
+    #[msg("Price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Start prices have already been recorded")]
+    StartPricesAlreadyRecorded,
+    #[msg("Start prices have not been recorded yet")]
+    StartPricesNotRecorded,
+    #[msg("War has been cancelled")]
+    WarCancelled,
+    #[msg("War has not been cancelled")]
+    WarNotCancelled,
+    #[msg("Instruction is not allowed in the war's current status")]
+    InvalidWarStatus,
+    #[msg("War has a settlement pending")]
+    SettlementPending,
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Token account does not match the war's betting mint")]
+    InvalidMint,
+    #[msg("Price update is for a different feed than the one registered")]
+    FeedIdMismatch,
+    #[msg("End price exponent differs from the start price exponent")]
+    ExponentChanged,
+    #[msg("Mint has a transfer fee, permanent delegate or transfer hook")]
+    UnsupportedMint,
 }
\ No newline at end of file
@@ -0,0 +1,176 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use betting_contract::{instruction, Bet, BettingError, War, WarStatus, SETTLEMENT_DEADLINE};
+use common::{
+    bet_address, error_code, process, set_clock, vault_account, vault_address, war_state,
+    TestAccount, BET_SPACE, WAR_SPACE,
+};
+
+const STAKE: u64 = 1_000_000_000;
+
+fn cancel_accounts(war: &War, caller: Pubkey) -> [TestAccount; 2] {
+    [
+        TestAccount::program_owned(Pubkey::new_unique(), war, WAR_SPACE),
+        TestAccount::system(caller, 0).signed(),
+    ]
+}
+
+#[test]
+fn authority_can_cancel_before_settlement() {
+    set_clock(1_500);
+    let war = war_state(0);
+    let mut accounts = cancel_accounts(&war, war.authority);
+    process(instruction::CancelWar {}, &mut accounts).unwrap();
+    assert_eq!(accounts[0].state::<War>().status, WarStatus::Cancelled);
+}
+
+#[test]
+fn others_must_wait_for_the_settlement_deadline() {
+    let war = war_state(0);
+
+    set_clock(war.end_time + SETTLEMENT_DEADLINE - 1);
+    let mut accounts = cancel_accounts(&war, Pubkey::new_unique());
+    assert_eq!(
+        process(instruction::CancelWar {}, &mut accounts),
+        error_code(BettingError::Unauthorized)
+    );
+
+    set_clock(war.end_time + SETTLEMENT_DEADLINE);
+    let mut accounts = cancel_accounts(&war, Pubkey::new_unique());
+    process(instruction::CancelWar {}, &mut accounts).unwrap();
+    assert_eq!(accounts[0].state::<War>().status, WarStatus::Cancelled);
+}
+
+#[test]
+fn only_authority_can_cancel_a_pending_or_disputed_result() {
+    // Even past the settlement deadline, nobody else may throw away a proposed result
+    let settling = WarStatus::Settling {
+        winner: Some(0),
+        proposed_at: 2_030,
+    };
+    for status in [settling, WarStatus::Disputed] {
+        let war = War {
+            status,
+            ..war_state(0)
+        };
+        set_clock(war.end_time + SETTLEMENT_DEADLINE);
+        let mut accounts = cancel_accounts(&war, Pubkey::new_unique());
+        assert_eq!(
+            process(instruction::CancelWar {}, &mut accounts),
+            error_code(BettingError::Unauthorized)
+        );
+
+        let mut accounts = cancel_accounts(&war, war.authority);
+        process(instruction::CancelWar {}, &mut accounts).unwrap();
+        assert_eq!(accounts[0].state::<War>().status, WarStatus::Cancelled);
+    }
+}
+
+#[test]
+fn settled_war_cannot_be_cancelled() {
+    set_clock(3_000);
+    let war = War {
+        status: WarStatus::Settled { winner: Some(0) },
+        ..war_state(0)
+    };
+    let mut accounts = cancel_accounts(&war, war.authority);
+    assert_eq!(
+        process(instruction::CancelWar {}, &mut accounts),
+        error_code(BettingError::WarAlreadySettled)
+    );
+}
+
+fn refund_accounts(war: War, user: TestAccount, vault_amount: u64) -> [TestAccount; 7] {
+    let war_key = Pubkey::new_unique();
+    let (_, vault_bump) = vault_address(&war_key);
+    let (bet_key, bet_bump) = bet_address(&war_key, &user.key, 0);
+    let bet = Bet {
+        user: user.key,
+        war: war_key,
+        amount: STAKE,
+        token_choice: 1,
+        timestamp: 1_500,
+        nonce: 0,
+        is_claimed: false,
+        bump: bet_bump,
+    };
+    let war = War { vault_bump, ..war };
+    [
+        TestAccount::program_owned(war_key, &war, WAR_SPACE),
+        TestAccount::program_owned(bet_key, &bet, BET_SPACE),
+        vault_account(&war_key, war.bet_mint, vault_amount),
+        TestAccount::mint(war.bet_mint, 9),
+        TestAccount::token_account(Pubkey::new_unique(), war.bet_mint, user.key, 0),
+        user,
+        TestAccount::token_program(),
+    ]
+}
+
+#[test]
+fn refund_returns_full_stake_once() {
+    set_clock(1_800);
+    let war = War {
+        total_bets_a: 3 * STAKE,
+        total_bets_b: STAKE,
+        status: WarStatus::Cancelled,
+        ..war_state(0)
+    };
+    let mut accounts = refund_accounts(war, TestAccount::signer(0), 4 * STAKE);
+
+    process(instruction::RefundBet {}, &mut accounts).unwrap();
+    assert_eq!(accounts[4].token_amount(), STAKE);
+    assert_eq!(accounts[2].token_amount(), 3 * STAKE);
+    assert!(accounts[1].state::<Bet>().is_claimed);
+
+    assert_eq!(
+        process(instruction::RefundBet {}, &mut accounts),
+        error_code(BettingError::AlreadyClaimed)
+    );
+    assert_eq!(accounts[4].token_amount(), STAKE);
+}
+
+#[test]
+fn refund_requires_cancelled_war() {
+    set_clock(3_000);
+    let war = War {
+        total_bets_b: STAKE,
+        status: WarStatus::Settled { winner: Some(0) },
+        ..war_state(0)
+    };
+    let mut accounts = refund_accounts(war, TestAccount::signer(0), 2 * STAKE);
+    assert_eq!(
+        process(instruction::RefundBet {}, &mut accounts),
+        error_code(BettingError::WarNotCancelled)
+    );
+}
+
+#[test]
+fn cancelled_war_rejects_claims_and_settlement() {
+    set_clock(3_000);
+    let war = War {
+        total_bets_b: STAKE,
+        status: WarStatus::Cancelled,
+        ..war_state(0)
+    };
+    let mut accounts = refund_accounts(war.clone(), TestAccount::signer(0), 2 * STAKE);
+    assert_eq!(
+        process(instruction::ClaimWinnings {}, &mut accounts),
+        error_code(BettingError::WarNotSettled)
+    );
+
+    let mut accounts = [
+        TestAccount::program_owned(Pubkey::new_unique(), &war, WAR_SPACE),
+        TestAccount::system(war.authority, 0).signed(),
+    ];
+    assert_eq!(
+        process(
+            instruction::SettleWar {
+                token_a_price_change: 100,
+                token_b_price_change: 0,
+            },
+            &mut accounts
+        ),
+        error_code(BettingError::WarCancelled)
+    );
+}
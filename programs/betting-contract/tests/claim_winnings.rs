@@ -0,0 +1,162 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use betting_contract::{instruction, Bet, BettingError, War, WarStatus};
+use common::{
+    bet_address, error_code, process, set_clock, vault_account, vault_address, war_state,
+    TestAccount, BET_SPACE, WAR_SPACE,
+};
+
+#[test]
+fn bets_from_one_wallet_use_distinct_nonce_addresses() {
+    set_clock(1_500);
+    let war_key = Pubkey::new_unique();
+    let (_, vault_bump) = vault_address(&war_key);
+    let state = war_state(vault_bump);
+    let mint = state.bet_mint;
+    let mut user = TestAccount::signer(10_000_000_000);
+    let user_key = user.key;
+    let mut user_tokens = TestAccount::token_account(Pubkey::new_unique(), mint, user_key, 3_000_000);
+
+    let mut war = TestAccount::program_owned(war_key, &state, WAR_SPACE);
+    let mut vault = vault_account(&war_key, mint, 0);
+
+    for (nonce, amount) in [(0u64, 1_000_000u64), (1, 2_000_000)] {
+        let (bet_key, bet_bump) = bet_address(&war_key, &user_key, nonce);
+        let mut accounts = [
+            war,
+            TestAccount::uninitialized(bet_key, BET_SPACE),
+            vault,
+            TestAccount::mint(mint, 9),
+            user_tokens,
+            user,
+            TestAccount::token_program(),
+            TestAccount::system_program(),
+        ];
+        process(
+            instruction::PlaceBet {
+                amount,
+                token_choice: 0,
+                nonce,
+            },
+            &mut accounts,
+        )
+        .unwrap();
+
+        let bet: Bet = accounts[1].state();
+        assert_eq!(bet.nonce, nonce);
+        assert_eq!(bet.bump, bet_bump);
+        assert_eq!(bet.amount, amount);
+        assert!(!bet.is_claimed);
+
+        let [w, _, v, _, t, u, ..] = accounts;
+        (war, vault, user_tokens, user) = (w, v, t, u);
+    }
+
+    assert_eq!(war.state::<War>().total_bets_a, 3_000_000);
+    assert_eq!(vault.token_amount(), 3_000_000);
+    assert_eq!(user_tokens.token_amount(), 0);
+}
+
+fn claim_accounts(war: War, token_choice: u8, amount: u64, vault_amount: u64) -> [TestAccount; 7] {
+    let war_key = Pubkey::new_unique();
+    let (_, vault_bump) = vault_address(&war_key);
+    let user = TestAccount::signer(0);
+    let (bet_key, bet_bump) = bet_address(&war_key, &user.key, 7);
+    let bet = Bet {
+        user: user.key,
+        war: war_key,
+        amount,
+        token_choice,
+        timestamp: 1_500,
+        nonce: 7,
+        is_claimed: false,
+        bump: bet_bump,
+    };
+    let war = War { vault_bump, ..war };
+    [
+        TestAccount::program_owned(war_key, &war, WAR_SPACE),
+        TestAccount::program_owned(bet_key, &bet, BET_SPACE),
+        vault_account(&war_key, war.bet_mint, vault_amount),
+        TestAccount::mint(war.bet_mint, 9),
+        TestAccount::token_account(Pubkey::new_unique(), war.bet_mint, user.key, 0),
+        user,
+        TestAccount::token_program(),
+    ]
+}
+
+#[test]
+fn winning_bet_cannot_be_claimed_twice() {
+    set_clock(3_000);
+    let war = War {
+        total_bets_a: 1_000_000,
+        total_bets_b: 1_000_000,
+        status: WarStatus::Settled { winner: Some(0) },
+        ..war_state(0)
+    };
+    let mut accounts = claim_accounts(war, 0, 1_000_000, 2_000_000);
+
+    process(instruction::ClaimWinnings {}, &mut accounts).unwrap();
+    assert!(accounts[1].state::<Bet>().is_claimed);
+    assert_eq!(accounts[2].token_amount(), 60_000);
+    assert_eq!(accounts[4].token_amount(), 1_940_000);
+
+    assert_eq!(
+        process(instruction::ClaimWinnings {}, &mut accounts),
+        error_code(BettingError::AlreadyClaimed)
+    );
+    assert_eq!(accounts[2].token_amount(), 60_000);
+    assert_eq!(accounts[4].token_amount(), 1_940_000);
+}
+
+#[test]
+fn losing_bet_cannot_be_claimed_twice() {
+    set_clock(3_000);
+    let war = War {
+        total_bets_a: 1_000_000,
+        total_bets_b: 1_000_000,
+        status: WarStatus::Settled { winner: Some(1) },
+        ..war_state(0)
+    };
+    let mut accounts = claim_accounts(war, 0, 1_000_000, 2_000_000);
+
+    process(instruction::ClaimWinnings {}, &mut accounts).unwrap();
+    assert_eq!(accounts[4].token_amount(), 0);
+    assert_eq!(
+        process(instruction::ClaimWinnings {}, &mut accounts),
+        error_code(BettingError::AlreadyClaimed)
+    );
+}
+
+#[test]
+fn large_pool_payout_does_not_overflow() {
+    set_clock(3_000);
+    // A 5e9 stake times a 9.7e9 prize pool overflows u64 before dividing
+    let war = War {
+        total_bets_a: 5_000_000_000,
+        total_bets_b: 5_000_000_000,
+        status: WarStatus::Settled { winner: Some(0) },
+        ..war_state(0)
+    };
+    let mut accounts = claim_accounts(war, 0, 5_000_000_000, 10_000_000_000);
+
+    process(instruction::ClaimWinnings {}, &mut accounts).unwrap();
+    assert_eq!(accounts[4].token_amount(), 9_700_000_000);
+}
+
+#[test]
+fn claims_must_use_the_war_mint() {
+    set_clock(3_000);
+    let war = War {
+        total_bets_a: 1_000_000,
+        total_bets_b: 1_000_000,
+        status: WarStatus::Settled { winner: Some(0) },
+        ..war_state(0)
+    };
+    let mut accounts = claim_accounts(war, 0, 1_000_000, 2_000_000);
+    accounts[3] = TestAccount::mint(Pubkey::new_unique(), 9);
+    assert_eq!(
+        process(instruction::ClaimWinnings {}, &mut accounts),
+        error_code(BettingError::InvalidMint)
+    );
+}
@@ -0,0 +1,86 @@
+//! `betting_contract` accounts and addresses on top of the shared
+//! `test_harness`, which drives the program with locally crafted accounts.
+
+#![allow(dead_code, unused_imports)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::InstructionData;
+use betting_contract::{War, WarStatus};
+
+pub use test_harness::{
+    ata_address, error_code, price_message, return_data, set_clock, token_account_space,
+    TestAccount,
+};
+
+pub const WAR_SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + WarStatus::SPACE + 1 + 32 + 32
+    + 8 + 8 + 8 + 8 + 8 + 32 + 32 + 32 + 32 + 32 + 4 + 4;
+pub const BET_SPACE: usize = 8 + 32 + 32 + 8 + 1 + 8 + 8 + 1 + 1;
+
+pub fn vault_address(war: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vault", war.as_ref()], &betting_contract::ID)
+}
+
+/// The war's vault token account, holding `amount` of `mint`.
+pub fn vault_account(war: &Pubkey, mint: Pubkey, amount: u64) -> TestAccount {
+    let vault = vault_address(war).0;
+    TestAccount::token_account(vault, mint, vault, amount)
+}
+
+/// Accounts for `finalize_settlement`; the crankers' bounty ATAs don't exist yet.
+pub fn finalize_accounts(war_key: Pubkey, war: &War, vault_amount: u64) -> [TestAccount; 11] {
+    [
+        TestAccount::program_owned(war_key, war, WAR_SPACE),
+        vault_account(&war_key, war.bet_mint, vault_amount),
+        TestAccount::mint(war.bet_mint, 9),
+        TestAccount::system(war.settle_cranker, 0),
+        TestAccount::uninitialized(ata_address(&war.settle_cranker, &war.bet_mint), 165),
+        TestAccount::system(war.snapshot_cranker, 0),
+        TestAccount::uninitialized(ata_address(&war.snapshot_cranker, &war.bet_mint), 165),
+        TestAccount::signer(1_000_000_000),
+        TestAccount::token_program(),
+        TestAccount::associated_token_program(),
+        TestAccount::system_program(),
+    ]
+}
+
+pub fn bet_address(war: &Pubkey, user: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"bet", war.as_ref(), user.as_ref(), &nonce.to_le_bytes()],
+        &betting_contract::ID,
+    )
+}
+
+pub fn war_state(vault_bump: u8) -> War {
+    let (token_a_price_feed, token_b_price_feed) = (Pubkey::new_unique(), Pubkey::new_unique());
+    War {
+        authority: Pubkey::new_unique(),
+        token_a: Pubkey::new_unique(),
+        token_b: Pubkey::new_unique(),
+        start_time: 1_000,
+        end_time: 2_000,
+        total_bets_a: 0,
+        total_bets_b: 0,
+        status: WarStatus::Open,
+        vault_bump,
+        token_a_price_feed,
+        token_b_price_feed,
+        token_a_start_price: 0,
+        token_b_start_price: 0,
+        token_a_end_price: 0,
+        token_b_end_price: 0,
+        bounties_paid: 0,
+        snapshot_cranker: Pubkey::default(),
+        settle_cranker: Pubkey::default(),
+        bet_mint: Pubkey::new_unique(),
+        token_a_feed_id: token_a_price_feed.to_bytes(),
+        token_b_feed_id: token_b_price_feed.to_bytes(),
+        token_a_exponent: 0,
+        token_b_exponent: 0,
+    }
+}
+
+/// Runs one instruction through the `betting_contract` entrypoint.
+pub fn process<I: InstructionData>(ix: I, accounts: &mut [TestAccount]) -> ProgramResult {
+    test_harness::process(betting_contract::entry, &betting_contract::ID, ix, accounts)
+}
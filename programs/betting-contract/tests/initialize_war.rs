@@ -0,0 +1,67 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use betting_contract::{instruction, BettingError, War, WarStatus};
+use common::{
+    error_code, process, set_clock, token_account_space, vault_address, TestAccount, WAR_SPACE,
+};
+
+fn initialize(
+    mint: TestAccount,
+    extensions: &[ExtensionType],
+) -> std::result::Result<War, ProgramError> {
+    let war = Pubkey::new_unique();
+    let (feed_a, feed_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut accounts = [
+        TestAccount::uninitialized(war, WAR_SPACE).signed(),
+        TestAccount::uninitialized(vault_address(&war).0, token_account_space(extensions)),
+        mint,
+        TestAccount::signer(10_000_000_000),
+        TestAccount::token_2022_program(),
+        TestAccount::system_program(),
+    ];
+    process(
+        instruction::InitializeWar {
+            token_a: Pubkey::new_unique(),
+            token_b: Pubkey::new_unique(),
+            start_time: 1_000,
+            end_time: 2_000,
+            token_a_price_feed: feed_a,
+            token_b_price_feed: feed_b,
+            token_a_feed_id: feed_a.to_bytes(),
+            token_b_feed_id: feed_b.to_bytes(),
+        },
+        &mut accounts,
+    )?;
+    Ok(accounts[0].state())
+}
+
+#[test]
+fn token_2022_mint_without_risky_extensions_is_accepted() {
+    set_clock(900);
+    let extensions = [ExtensionType::MintCloseAuthority];
+    let mint = TestAccount::mint_2022(Pubkey::new_unique(), 6, &extensions);
+    let mint_key = mint.key;
+    let war = initialize(mint, &extensions).unwrap();
+    assert_eq!(war.bet_mint, mint_key);
+    assert_eq!(war.status, WarStatus::Open);
+}
+
+#[test]
+fn mints_that_can_shortchange_or_drain_the_vault_are_rejected() {
+    set_clock(900);
+    // A 0 bps fee is still rejected: its authority can raise it mid-war
+    for extension in [
+        ExtensionType::TransferFeeConfig,
+        ExtensionType::PermanentDelegate,
+        ExtensionType::TransferHook,
+    ] {
+        let mint = TestAccount::mint_2022(Pubkey::new_unique(), 6, &[extension]);
+        assert_eq!(
+            initialize(mint, &[extension]).map(|_| ()),
+            error_code(BettingError::UnsupportedMint),
+            "{extension:?}"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use betting_contract::{instruction, Bet, BettingError, War, WarStatus};
+use common::{
+    bet_address, error_code, process, return_data, set_clock, vault_account, vault_address,
+    war_state, TestAccount, BET_SPACE, WAR_SPACE,
+};
+use payout::BetQuote;
+
+#[test]
+fn quote_matches_the_settled_payout() {
+    set_clock(1_500);
+    let war_key = Pubkey::new_unique();
+    let (_, vault_bump) = vault_address(&war_key);
+    let war = War {
+        total_bets_a: 1_000_000,
+        total_bets_b: 3_000_000,
+        ..war_state(vault_bump)
+    };
+    let mint = war.bet_mint;
+    let mut war = TestAccount::program_owned(war_key, &war, WAR_SPACE);
+
+    process(
+        instruction::QuoteBet {
+            amount: 1_000_000,
+            token_choice: 0,
+        },
+        std::slice::from_mut(&mut war),
+    )
+    .unwrap();
+    let quote: BetQuote = return_data();
+    assert_eq!(
+        quote,
+        BetQuote {
+            stake: 1_000_000,
+            projected_payout: 2_425_000,
+            odds_bps: 24_250,
+        }
+    );
+
+    // Place the quoted bet, settle with token A winning and claim
+    let user = TestAccount::signer(1_000_000_000);
+    let user_key = user.key;
+    let (bet_key, _) = bet_address(&war_key, &user_key, 0);
+    let mut accounts = [
+        war,
+        TestAccount::uninitialized(bet_key, BET_SPACE),
+        vault_account(&war_key, mint, 4_000_000),
+        TestAccount::mint(mint, 9),
+        TestAccount::token_account(Pubkey::new_unique(), mint, user_key, 1_000_000),
+        user,
+        TestAccount::token_program(),
+        TestAccount::system_program(),
+    ];
+    process(
+        instruction::PlaceBet {
+            amount: 1_000_000,
+            token_choice: 0,
+            nonce: 0,
+        },
+        &mut accounts,
+    )
+    .unwrap();
+
+    let mut settled: War = accounts[0].state();
+    settled.status = WarStatus::Settled { winner: Some(0) };
+    accounts[0] = TestAccount::program_owned(war_key, &settled, WAR_SPACE);
+    process(instruction::ClaimWinnings {}, &mut accounts).unwrap();
+
+    assert!(accounts[1].state::<Bet>().is_claimed);
+    assert_eq!(accounts[4].token_amount(), quote.projected_payout);
+}
+
+#[test]
+fn quote_rejects_empty_and_unknown_bets() {
+    let mut war = [TestAccount::program_owned(
+        Pubkey::new_unique(),
+        &war_state(0),
+        WAR_SPACE,
+    )];
+
+    assert_eq!(
+        process(
+            instruction::QuoteBet {
+                amount: 0,
+                token_choice: 0,
+            },
+            &mut war,
+        ),
+        error_code(BettingError::InvalidAmount)
+    );
+    assert_eq!(
+        process(
+            instruction::QuoteBet {
+                amount: 1_000_000,
+                token_choice: 2,
+            },
+            &mut war,
+        ),
+        error_code(BettingError::InvalidTokenChoice)
+    );
+}
@@ -0,0 +1,363 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use betting_contract::oracle::{self, PriceFeedMessage};
+use betting_contract::{instruction, Bet, BettingError, War, WarStatus, DISPUTE_WINDOW};
+use common::{
+    ata_address, bet_address, error_code, finalize_accounts, price_message, process, set_clock,
+    vault_address, war_state, TestAccount, BET_SPACE, WAR_SPACE,
+};
+
+const PRICE: u64 = 100_000_000;
+const WALLET: u64 = 1_000_000_000;
+
+struct Fixture {
+    war_key: Pubkey,
+    war: War,
+    vault_amount: u64,
+    snapshot_cranker: Pubkey,
+    settle_cranker: Pubkey,
+}
+
+impl Fixture {
+    fn new(total_bets_a: u64, total_bets_b: u64) -> Self {
+        let war_key = Pubkey::new_unique();
+        let (_, vault_bump) = vault_address(&war_key);
+        let war = War {
+            total_bets_a,
+            total_bets_b,
+            ..war_state(vault_bump)
+        };
+        Self {
+            war_key,
+            war,
+            vault_amount: total_bets_a + total_bets_b,
+            snapshot_cranker: Pubkey::new_unique(),
+            settle_cranker: Pubkey::new_unique(),
+        }
+    }
+
+    fn war(&self) -> TestAccount {
+        TestAccount::program_owned(self.war_key, &self.war, WAR_SPACE)
+    }
+
+    fn snapshot_accounts(&self, feed_a: TestAccount, feed_b: TestAccount) -> [TestAccount; 4] {
+        [
+            self.war(),
+            feed_a,
+            feed_b,
+            TestAccount::system(self.snapshot_cranker, WALLET).signed(),
+        ]
+    }
+
+    fn settle_accounts(&self, feed_a: TestAccount, feed_b: TestAccount) -> [TestAccount; 4] {
+        [
+            self.war(),
+            feed_a,
+            feed_b,
+            TestAccount::system(self.settle_cranker, WALLET).signed(),
+        ]
+    }
+
+    fn finalize_accounts(&self) -> [TestAccount; 11] {
+        finalize_accounts(self.war_key, &self.war, self.vault_amount)
+    }
+
+    fn feeds(&self, price_a: u64, price_b: u64, publish_time: i64) -> (TestAccount, TestAccount) {
+        (
+            TestAccount::price_feed(self.war.token_a_price_feed, price_a, 0, publish_time),
+            TestAccount::price_feed(self.war.token_b_price_feed, price_b, 0, publish_time),
+        )
+    }
+
+    fn with_start_prices(mut self) -> Self {
+        self.war.token_a_start_price = PRICE;
+        self.war.token_b_start_price = PRICE;
+        self.war.token_a_exponent = -8;
+        self.war.token_b_exponent = -8;
+        self.war.snapshot_cranker = self.snapshot_cranker;
+        self
+    }
+
+    // An oracle result proposed at 2_030 and still inside the dispute window
+    fn proposed(mut self, winner: Option<u8>) -> Self {
+        self = self.with_start_prices();
+        self.war.settle_cranker = self.settle_cranker;
+        self.war.status = WarStatus::Settling {
+            winner,
+            proposed_at: 2_030,
+        };
+        self
+    }
+}
+
+#[test]
+fn anyone_can_snapshot_and_settle_from_feeds() {
+    let mut fixture = Fixture::new(1_000_000_000, 1_000_000_000);
+
+    set_clock(1_010);
+    let (feed_a, feed_b) = fixture.feeds(PRICE, PRICE, 1_005);
+    let mut accounts = fixture.snapshot_accounts(feed_a, feed_b);
+    process(instruction::SnapshotStartPrice {}, &mut accounts).unwrap();
+    fixture.war = accounts[0].state();
+    assert_eq!(fixture.war.token_a_start_price, PRICE);
+    assert_eq!(fixture.war.token_b_start_price, PRICE);
+    assert_eq!(fixture.war.token_a_exponent, -8);
+    assert_eq!(fixture.war.snapshot_cranker, fixture.snapshot_cranker);
+
+    // Token A falls 5%, token B falls 10%: the smaller loss wins
+    set_clock(2_030);
+    let (feed_a, feed_b) = fixture.feeds(PRICE * 95 / 100, PRICE * 90 / 100, 2_020);
+    let mut accounts = fixture.settle_accounts(feed_a, feed_b);
+    process(instruction::SettleWarPermissionless {}, &mut accounts).unwrap();
+    fixture.war = accounts[0].state();
+    assert_eq!(
+        fixture.war.status,
+        WarStatus::Settling {
+            winner: Some(0),
+            proposed_at: 2_030,
+        }
+    );
+    assert_eq!(fixture.war.token_a_end_price, PRICE * 95 / 100);
+    assert_eq!(fixture.war.token_b_end_price, PRICE * 90 / 100);
+    assert_eq!(fixture.war.settle_cranker, fixture.settle_cranker);
+
+    // Both cranks are paid 5% of the 60_000_000 platform fee once the result stands,
+    // into token accounts finalize opens for them
+    set_clock(2_030 + DISPUTE_WINDOW);
+    let mut accounts = fixture.finalize_accounts();
+    process(instruction::FinalizeSettlement {}, &mut accounts).unwrap();
+    assert_eq!(accounts[4].token_amount(), 3_000_000);
+    assert_eq!(accounts[6].token_amount(), 3_000_000);
+
+    let war: War = accounts[0].state();
+    assert_eq!(war.status, WarStatus::Settled { winner: Some(0) });
+    assert_eq!(war.bounties_paid, 6_000_000);
+}
+
+#[test]
+fn bounty_is_a_share_of_platform_fee() {
+    // 3% of a 100_000 pool is a 3_000 fee, 5% of which goes to each crank
+    let fixture = Fixture::new(50_000, 50_000).proposed(Some(0));
+
+    set_clock(2_030 + DISPUTE_WINDOW);
+    let mut accounts = fixture.finalize_accounts();
+    process(instruction::FinalizeSettlement {}, &mut accounts).unwrap();
+    assert_eq!(accounts[4].token_amount(), 150);
+    assert_eq!(accounts[6].token_amount(), 150);
+    assert_eq!(accounts[1].token_amount(), 99_700);
+}
+
+#[test]
+fn tie_pays_no_bounties_and_refunds_every_bettor() {
+    let fixture = Fixture::new(1_000_000_000, 3_000_000_000).proposed(None);
+
+    set_clock(2_030 + DISPUTE_WINDOW);
+    let mut accounts = fixture.finalize_accounts();
+    process(instruction::FinalizeSettlement {}, &mut accounts).unwrap();
+    assert_eq!(accounts[4].token_amount(), 0);
+    assert_eq!(accounts[6].token_amount(), 0);
+    let [mut war, mut vault, mut mint, ..] = accounts;
+    assert_eq!(war.state::<War>().bounties_paid, 0);
+
+    for (token_choice, amount) in [(0, 1_000_000_000), (1, 3_000_000_000)] {
+        let user = TestAccount::signer(0);
+        let (bet_key, bump) = bet_address(&fixture.war_key, &user.key, 0);
+        let bet = Bet {
+            user: user.key,
+            war: fixture.war_key,
+            amount,
+            token_choice,
+            timestamp: 1_000,
+            nonce: 0,
+            is_claimed: false,
+            bump,
+        };
+        let user_tokens =
+            TestAccount::token_account(Pubkey::new_unique(), fixture.war.bet_mint, user.key, 0);
+        let mut accounts = [
+            war,
+            TestAccount::program_owned(bet_key, &bet, BET_SPACE),
+            vault,
+            mint,
+            user_tokens,
+            user,
+            TestAccount::token_program(),
+        ];
+        process(instruction::ClaimWinnings {}, &mut accounts).unwrap();
+        assert_eq!(accounts[4].token_amount(), amount);
+        [war, _, vault, mint, ..] = accounts;
+    }
+    assert_eq!(vault.token_amount(), 0);
+}
+
+#[test]
+fn snapshot_bounty_goes_to_recorded_cranker() {
+    let fixture = Fixture::new(1_000_000_000, 0).proposed(Some(0));
+
+    set_clock(2_030 + DISPUTE_WINDOW);
+    let mut accounts = fixture.finalize_accounts();
+    let impostor = Pubkey::new_unique();
+    accounts[5] = TestAccount::system(impostor, WALLET);
+    accounts[6] = TestAccount::uninitialized(ata_address(&impostor, &fixture.war.bet_mint), 165);
+    assert_eq!(
+        process(instruction::FinalizeSettlement {}, &mut accounts),
+        error_code(BettingError::Unauthorized)
+    );
+}
+
+#[test]
+fn snapshot_waits_for_start_time() {
+    let fixture = Fixture::new(0, 0);
+    set_clock(990);
+    let (feed_a, feed_b) = fixture.feeds(PRICE, PRICE, 985);
+    let mut accounts = fixture.snapshot_accounts(feed_a, feed_b);
+    assert_eq!(
+        process(instruction::SnapshotStartPrice {}, &mut accounts),
+        error_code(BettingError::WarNotStarted)
+    );
+}
+
+#[test]
+fn settlement_waits_for_end_time() {
+    let fixture = Fixture::new(0, 0).with_start_prices();
+    set_clock(1_990);
+    let (feed_a, feed_b) = fixture.feeds(PRICE, PRICE, 1_985);
+    let mut accounts = fixture.settle_accounts(feed_a, feed_b);
+    assert_eq!(
+        process(instruction::SettleWarPermissionless {}, &mut accounts),
+        error_code(BettingError::WarNotEnded)
+    );
+}
+
+#[test]
+fn settlement_requires_start_prices() {
+    let fixture = Fixture::new(0, 0);
+    set_clock(2_030);
+    let (feed_a, feed_b) = fixture.feeds(PRICE, PRICE, 2_020);
+    let mut accounts = fixture.settle_accounts(feed_a, feed_b);
+    assert_eq!(
+        process(instruction::SettleWarPermissionless {}, &mut accounts),
+        error_code(BettingError::StartPricesNotRecorded)
+    );
+}
+
+#[test]
+fn start_price_is_snapshotted_once() {
+    let fixture = Fixture::new(0, 0).with_start_prices();
+    set_clock(1_010);
+    let (feed_a, feed_b) = fixture.feeds(PRICE, PRICE, 1_005);
+    let mut accounts = fixture.snapshot_accounts(feed_a, feed_b);
+    assert_eq!(
+        process(instruction::SnapshotStartPrice {}, &mut accounts),
+        error_code(BettingError::StartPricesAlreadyRecorded)
+    );
+}
+
+#[test]
+fn rejects_unregistered_feed() {
+    let fixture = Fixture::new(0, 0);
+    set_clock(1_010);
+    let (_, feed_b) = fixture.feeds(PRICE, PRICE, 1_005);
+    let impostor = TestAccount::price_feed(Pubkey::new_unique(), PRICE, 0, 1_005);
+    let mut accounts = fixture.snapshot_accounts(impostor, feed_b);
+    assert_eq!(
+        process(instruction::SnapshotStartPrice {}, &mut accounts),
+        error_code(BettingError::InvalidPriceFeed)
+    );
+}
+
+#[test]
+fn rejects_feed_not_owned_by_oracle() {
+    let fixture = Fixture::new(0, 0);
+    set_clock(1_010);
+    let (mut feed_a, feed_b) = fixture.feeds(PRICE, PRICE, 1_005);
+    feed_a.owner = Pubkey::new_unique();
+    let mut accounts = fixture.snapshot_accounts(feed_a, feed_b);
+    assert_eq!(
+        process(instruction::SnapshotStartPrice {}, &mut accounts),
+        error_code(BettingError::InvalidPriceFeed)
+    );
+}
+
+#[test]
+fn rejects_stale_price() {
+    let fixture = Fixture::new(0, 0);
+    set_clock(1_005 + oracle::MAX_PRICE_AGE + 1);
+    let (feed_a, feed_b) = fixture.feeds(PRICE, PRICE, 1_005);
+    let mut accounts = fixture.snapshot_accounts(feed_a, feed_b);
+    assert_eq!(
+        process(instruction::SnapshotStartPrice {}, &mut accounts),
+        error_code(BettingError::StalePrice)
+    );
+}
+
+#[test]
+fn rejects_price_published_before_start() {
+    let fixture = Fixture::new(0, 0);
+    set_clock(1_010);
+    let (feed_a, feed_b) = fixture.feeds(PRICE, PRICE, 995);
+    let mut accounts = fixture.snapshot_accounts(feed_a, feed_b);
+    assert_eq!(
+        process(instruction::SnapshotStartPrice {}, &mut accounts),
+        error_code(BettingError::PriceOutsideWindow)
+    );
+}
+
+#[test]
+fn rejects_wide_confidence() {
+    let fixture = Fixture::new(0, 0);
+    set_clock(1_010);
+    let (_, feed_b) = fixture.feeds(PRICE, PRICE, 1_005);
+    let conf = PRICE * (oracle::MAX_CONFIDENCE_BPS + 1) / 10000;
+    let feed_a = TestAccount::price_feed(fixture.war.token_a_price_feed, PRICE, conf, 1_005);
+    let mut accounts = fixture.snapshot_accounts(feed_a, feed_b);
+    assert_eq!(
+        process(instruction::SnapshotStartPrice {}, &mut accounts),
+        error_code(BettingError::PriceConfidenceTooWide)
+    );
+}
+
+#[test]
+fn rejects_update_for_another_feed() {
+    let fixture = Fixture::new(0, 0);
+    set_clock(1_010);
+    let (_, feed_b) = fixture.feeds(PRICE, PRICE, 1_005);
+
+    // The registered account, rewritten by its write authority with another feed's price
+    let key = fixture.war.token_a_price_feed;
+    let feed_a = TestAccount::price_update(
+        key,
+        PriceFeedMessage {
+            feed_id: Pubkey::new_unique().to_bytes(),
+            ..price_message(key, PRICE, 0, 1_005)
+        },
+    );
+    let mut accounts = fixture.snapshot_accounts(feed_a, feed_b);
+    assert_eq!(
+        process(instruction::SnapshotStartPrice {}, &mut accounts),
+        error_code(BettingError::FeedIdMismatch)
+    );
+}
+
+#[test]
+fn rejects_end_price_in_another_exponent() {
+    let fixture = Fixture::new(0, 0).with_start_prices();
+    set_clock(2_030);
+    let (_, feed_b) = fixture.feeds(PRICE, PRICE, 2_020);
+
+    // 1.0 at exponent -6 reads as 100x the start price at exponent -8
+    let key = fixture.war.token_a_price_feed;
+    let feed_a = TestAccount::price_update(
+        key,
+        PriceFeedMessage {
+            exponent: -6,
+            ..price_message(key, 1_000_000, 0, 2_020)
+        },
+    );
+    let mut accounts = fixture.settle_accounts(feed_a, feed_b);
+    assert_eq!(
+        process(instruction::SettleWarPermissionless {}, &mut accounts),
+        error_code(BettingError::ExponentChanged)
+    );
+}
@@ -0,0 +1,245 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use betting_contract::{
+    instruction, Bet, BettingError, Rejection, War, WarStatus, DISPUTE_WINDOW,
+};
+use common::{
+    bet_address, error_code, finalize_accounts, process, set_clock, vault_account, vault_address,
+    war_state, TestAccount, BET_SPACE, WAR_SPACE,
+};
+
+const PROPOSED_AT: i64 = 2_030;
+
+fn all_statuses() -> [WarStatus; 6] {
+    [
+        WarStatus::Open,
+        WarStatus::Locked,
+        WarStatus::Settling {
+            winner: Some(0),
+            proposed_at: PROPOSED_AT,
+        },
+        WarStatus::Disputed,
+        WarStatus::Settled { winner: Some(1) },
+        WarStatus::Cancelled,
+    ]
+}
+
+fn legal(from: &WarStatus, to: &WarStatus) -> bool {
+    use WarStatus::*;
+    match from {
+        Open => matches!(to, Locked | Cancelled),
+        Locked => matches!(to, Settling { .. } | Settled { .. } | Cancelled),
+        Settling { .. } => matches!(to, Settled { .. } | Disputed | Cancelled),
+        Disputed => matches!(to, Settled { .. } | Cancelled),
+        Settled { .. } | Cancelled => false,
+    }
+}
+
+#[test]
+fn every_illegal_transition_is_rejected() {
+    for from in all_statuses() {
+        for to in all_statuses() {
+            let mut war = War {
+                status: from,
+                ..war_state(0)
+            };
+            let result = war.transition(to).map_err(ProgramError::from);
+            if legal(&from, &to) {
+                assert_eq!(result, Ok(()), "{from:?} -> {to:?}");
+                assert_eq!(war.status, to);
+            } else {
+                assert_eq!(result, error_code(from.rejection()), "{from:?} -> {to:?}");
+                assert_eq!(war.status, from);
+            }
+        }
+    }
+}
+
+fn war_account(status: WarStatus) -> (TestAccount, War) {
+    let war = War {
+        status,
+        ..war_state(0)
+    };
+    (
+        TestAccount::program_owned(Pubkey::new_unique(), &war, WAR_SPACE),
+        war,
+    )
+}
+
+#[test]
+fn bets_are_only_taken_while_open() {
+    set_clock(1_500);
+    for status in all_statuses().into_iter().skip(1) {
+        let war_key = Pubkey::new_unique();
+        let (_, vault_bump) = vault_address(&war_key);
+        let user = TestAccount::signer(1_000_000_000);
+        let (bet_key, _) = bet_address(&war_key, &user.key, 0);
+        let war = War {
+            status,
+            ..war_state(vault_bump)
+        };
+        let mut accounts = [
+            TestAccount::program_owned(war_key, &war, WAR_SPACE),
+            TestAccount::uninitialized(bet_key, BET_SPACE),
+            vault_account(&war_key, war.bet_mint, 0),
+            TestAccount::mint(war.bet_mint, 9),
+            TestAccount::token_account(Pubkey::new_unique(), war.bet_mint, user.key, 1_000_000),
+            user,
+            TestAccount::token_program(),
+            TestAccount::system_program(),
+        ];
+        assert_eq!(
+            process(
+                instruction::PlaceBet {
+                    amount: 1_000_000,
+                    token_choice: 0,
+                    nonce: 0,
+                },
+                &mut accounts
+            ),
+            error_code(status.rejection()),
+            "{status:?}"
+        );
+    }
+}
+
+#[test]
+fn authority_settlement_locks_an_ended_war() {
+    let (war, state) = war_account(WarStatus::Open);
+    let settle = || instruction::SettleWar {
+        token_a_price_change: 0,
+        token_b_price_change: 100,
+    };
+
+    set_clock(1_999);
+    let mut accounts = [war, TestAccount::system(state.authority, 0).signed()];
+    assert_eq!(
+        process(settle(), &mut accounts),
+        error_code(BettingError::WarNotEnded)
+    );
+
+    set_clock(2_000);
+    process(settle(), &mut accounts).unwrap();
+    assert_eq!(
+        accounts[0].state::<War>().status,
+        WarStatus::Settled { winner: Some(1) }
+    );
+}
+
+#[test]
+fn authority_can_dispute_within_window_and_resolve() {
+    let (war, state) = war_account(all_statuses()[2]);
+    let mut accounts = [war, TestAccount::system(state.authority, 0).signed()];
+
+    set_clock(PROPOSED_AT + DISPUTE_WINDOW - 1);
+    process(instruction::DisputeSettlement {}, &mut accounts).unwrap();
+    assert_eq!(accounts[0].state::<War>().status, WarStatus::Disputed);
+
+    // A disputed war only settles by hand
+    process(
+        instruction::SettleWar {
+            token_a_price_change: 0,
+            token_b_price_change: 100,
+        },
+        &mut accounts,
+    )
+    .unwrap();
+    assert_eq!(
+        accounts[0].state::<War>().status,
+        WarStatus::Settled { winner: Some(1) }
+    );
+}
+
+#[test]
+fn dispute_is_rejected_after_window_or_from_others() {
+    let (war, state) = war_account(all_statuses()[2]);
+
+    set_clock(PROPOSED_AT + 10);
+    let mut accounts = [war, TestAccount::signer(0)];
+    assert_eq!(
+        process(instruction::DisputeSettlement {}, &mut accounts),
+        error_code(BettingError::Unauthorized)
+    );
+
+    set_clock(PROPOSED_AT + DISPUTE_WINDOW);
+    accounts[1] = TestAccount::system(state.authority, 0).signed();
+    assert_eq!(
+        process(instruction::DisputeSettlement {}, &mut accounts),
+        error_code(BettingError::DisputeWindowClosed)
+    );
+}
+
+fn finalize_accounts_for(status: WarStatus) -> [TestAccount; 11] {
+    let war_key = Pubkey::new_unique();
+    let (_, vault_bump) = vault_address(&war_key);
+    let war = War {
+        status,
+        ..war_state(vault_bump)
+    };
+    finalize_accounts(war_key, &war, 0)
+}
+
+#[test]
+fn finalize_waits_for_dispute_window() {
+    set_clock(PROPOSED_AT + DISPUTE_WINDOW - 1);
+    let mut accounts = finalize_accounts_for(all_statuses()[2]);
+    assert_eq!(
+        process(instruction::FinalizeSettlement {}, &mut accounts),
+        error_code(BettingError::DisputeWindowOpen)
+    );
+
+    set_clock(PROPOSED_AT + DISPUTE_WINDOW);
+    process(instruction::FinalizeSettlement {}, &mut accounts).unwrap();
+    assert_eq!(
+        accounts[0].state::<War>().status,
+        WarStatus::Settled { winner: Some(0) }
+    );
+}
+
+#[test]
+fn disputed_result_cannot_be_finalized() {
+    set_clock(PROPOSED_AT + DISPUTE_WINDOW);
+    let mut accounts = finalize_accounts_for(WarStatus::Disputed);
+    assert_eq!(
+        process(instruction::FinalizeSettlement {}, &mut accounts),
+        error_code(BettingError::SettlementPending)
+    );
+}
+
+#[test]
+fn claims_wait_for_final_result() {
+    set_clock(PROPOSED_AT + 10);
+    let war_key = Pubkey::new_unique();
+    let (_, vault_bump) = vault_address(&war_key);
+    let user = TestAccount::signer(0);
+    let (bet_key, bet_bump) = bet_address(&war_key, &user.key, 0);
+    let war = War {
+        status: all_statuses()[2],
+        total_bets_a: 1_000_000,
+        ..war_state(vault_bump)
+    };
+    let bet = Bet {
+        user: user.key,
+        war: war_key,
+        amount: 1_000_000,
+        token_choice: 0,
+        timestamp: 1_500,
+        nonce: 0,
+        is_claimed: false,
+        bump: bet_bump,
+    };
+    let mut accounts = [
+        TestAccount::program_owned(war_key, &war, WAR_SPACE),
+        TestAccount::program_owned(bet_key, &bet, BET_SPACE),
+        vault_account(&war_key, war.bet_mint, 2_000_000),
+        TestAccount::mint(war.bet_mint, 9),
+        TestAccount::token_account(Pubkey::new_unique(), war.bet_mint, user.key, 0),
+        user,
+        TestAccount::token_program(),
+    ];
+    assert_eq!(
+        process(instruction::ClaimWinnings {}, &mut accounts),
+        error_code(BettingError::WarNotSettled)
+    );
+}
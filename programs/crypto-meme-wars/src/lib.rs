@@ -1,11 +1,24 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use war_token::StakeAccount;
+
+pub use war_status::{StatusError, WarStatus};
 
 // Re-export modules
-pub mod war_token;
-pub use war_token::*;
+pub mod oracle;
+
+declare_id!("7aZL2tA1uVhVSyjNsMW9kSGeuCgvVv46U2qWaAy97gsM");
 
-declare_id!("CryptoMemeWars11111111111111111111111111111111");
+pub const CRANK_BOUNTY_BPS: u64 = 500; // 5% of a war's platform fee per snapshot/settlement crank
+pub const SETTLEMENT_DEADLINE: i64 = 86_400; // anyone may cancel a war left unsettled this long after measure_end
+pub const DISPUTE_WINDOW: i64 = 3_600; // seconds the settler has to dispute an oracle settlement
+pub const MIN_CONTESTANTS: usize = 2;
+pub const MAX_CONTESTANTS: usize = 8; // battle-royale wars top out at 8 tokens
+pub const MAX_SYMBOL_LEN: usize = 16;
+pub const MAX_PLATFORM_FEE_BPS: u16 = 1_000; // update_platform_config can raise the fee to at most 10%
+pub const MAX_EXIT_PENALTY_BPS: u16 = 5_000;
+pub const MAX_ALLOWED_CREATORS: usize = 16;
 
 #[program]
 pub mod crypto_meme_wars {
@@ -15,9 +28,23 @@ pub mod crypto_meme_wars {
     pub fn initialize_platform(ctx: Context<InitializePlatform>) -> Result<()> {
         let platform_state = &mut ctx.accounts.platform_state;
         platform_state.authority = ctx.accounts.authority.key();
+        platform_state.pending_authority = None;
+        platform_state.roles = PlatformRoles {
+            pauser: ctx.accounts.authority.key(),
+            settler: ctx.accounts.authority.key(),
+            fee_manager: ctx.accounts.authority.key(),
+        };
+        platform_state.war_mint = ctx.accounts.war_mint.key();
+        platform_state.config = PlatformConfig {
+            platform_fee_bps: 300, // 3%
+            fee_collector: ctx.accounts.authority.key(),
+            min_duration_hours: 1,
+            max_duration_hours: 168, // 1 week
+            exit_penalty_bps: 500, // 5%
+            allowed_creators: Vec::new(),
+        };
         platform_state.total_bets = 0;
-        platform_state.total_volume = 0;
-        platform_state.platform_fee_bps = 300; // 3%
+        platform_state.war_count = 0;
         platform_state.is_paused = false;
         platform_state.bump = ctx.bumps.platform_state;
 
@@ -29,209 +56,520 @@ pub mod crypto_meme_wars {
         Ok(())
     }
 
-    // Create a new betting war between two tokens
+    // Create a new betting war between 2 to MAX_CONTESTANTS tokens, staked in `bet_mint`.
+    // The war may be scheduled to open for bets and start measuring in the future.
     pub fn create_war(
         ctx: Context<CreateWar>,
-        token_a_symbol: String,
-        token_b_symbol: String,
-        duration_hours: u32,
+        contestants: Vec<ContestantInit>,
+        schedule: WarSchedule,
     ) -> Result<()> {
+        let config = &ctx.accounts.platform_state.config;
+        let clock = Clock::get()?;
         require!(!ctx.accounts.platform_state.is_paused, ErrorCode::PlatformPaused);
-        require!(duration_hours >= 1 && duration_hours <= 168, ErrorCode::InvalidDuration); // 1 hour to 1 week
+        require!(config.may_create_wars(&ctx.accounts.creator.key()), ErrorCode::CreatorNotAllowed);
+        schedule.validate(config, clock.unix_timestamp)?;
+        require!(
+            (MIN_CONTESTANTS..=MAX_CONTESTANTS).contains(&contestants.len()),
+            ErrorCode::InvalidContestantCount
+        );
+        for (i, contestant) in contestants.iter().enumerate() {
+            require!(contestant.symbol.len() <= MAX_SYMBOL_LEN, ErrorCode::SymbolTooLong);
+            require!(
+                contestants[..i].iter().all(|other| {
+                    other.price_feed != contestant.price_feed && other.feed_id != contestant.feed_id
+                }),
+                ErrorCode::DuplicateContestant
+            );
+        }
 
         let war = &mut ctx.accounts.war;
-        let clock = Clock::get()?;
+        let platform_state = &mut ctx.accounts.platform_state;
+
+        // The war's address is seeded with this number, so clients can derive war #N
+        war.number = platform_state.war_count;
+        platform_state.war_count = payout::add(platform_state.war_count, 1)?;
 
         war.creator = ctx.accounts.creator.key();
-        war.token_a_symbol = token_a_symbol.clone();
-        war.token_b_symbol = token_b_symbol.clone();
-        war.start_time = clock.unix_timestamp;
-        war.end_time = clock.unix_timestamp + (duration_hours as i64 * 3600);
-        war.total_bets_a = 0;
-        war.total_bets_b = 0;
-        war.num_bets = 0;
-        war.is_settled = false;
-        war.winner = None;
+        war.bet_mint = ctx.accounts.bet_mint.key();
+        war.contestants = contestants
+            .into_iter()
+            .map(|contestant| Contestant {
+                symbol: contestant.symbol,
+                price_feed: contestant.price_feed,
+                feed_id: contestant.feed_id,
+                exponent: 0,
+                start_price: 0,
+                end_price: 0,
+                total_bets: 0,
+            })
+            .collect();
+        war.schedule = schedule;
+        war.num_bettors = 0;
+        war.status = WarStatus::Open;
+        war.fee_pool = 0;
+        war.penalty_pool = 0;
+        war.num_claimed = 0;
+        war.snapshot_cranker = Pubkey::default();
+        war.settle_cranker = Pubkey::default();
         war.bump = ctx.bumps.war;
+        war.escrow_bump = ctx.bumps.war_escrow;
 
         emit!(WarCreated {
             war_id: war.key(),
-            token_a: token_a_symbol,
-            token_b: token_b_symbol,
-            end_time: war.end_time,
+            number: war.number,
+            bet_mint: war.bet_mint,
+            symbols: war.contestants.iter().map(|c| c.symbol.clone()).collect(),
+            schedule: war.schedule,
         });
 
         Ok(())
     }
 
-    // Place a bet on a war
+    // Place a bet on a war; repeat bets from one wallet add to its position
     pub fn place_bet(
         ctx: Context<PlaceBet>,
         amount: u64,
-        token_choice: u8, // 0 for token A, 1 for token B
+        contestant: u8, // index into war.contestants
     ) -> Result<()> {
+        let config = &ctx.accounts.platform_state.config;
+        let mint_config = &ctx.accounts.mint_config;
         require!(!ctx.accounts.platform_state.is_paused, ErrorCode::PlatformPaused);
         require!(amount > 0, ErrorCode::InvalidAmount);
-        require!(token_choice <= 1, ErrorCode::InvalidTokenChoice);
+        require!(
+            (mint_config.min_bet..=mint_config.max_bet).contains(&amount),
+            ErrorCode::BetOutOfRange
+        );
 
         let war = &mut ctx.accounts.war;
         let bet = &mut ctx.accounts.bet;
         let clock = Clock::get()?;
 
-        require!(!war.is_settled, ErrorCode::WarAlreadySettled);
-        require!(clock.unix_timestamp < war.end_time, ErrorCode::WarEnded);
+        require!((contestant as usize) < war.contestants.len(), ErrorCode::InvalidTokenChoice);
+        require!(war.status == WarStatus::Open, war.status.rejection());
+        require!(clock.unix_timestamp >= war.schedule.betting_open, ErrorCode::BettingNotOpen);
+        require!(clock.unix_timestamp < war.schedule.betting_close, ErrorCode::WarEnded);
 
-        // Calculate platform fee
-        let platform_fee = amount * ctx.accounts.platform_state.platform_fee_bps as u64 / 10000;
-        let bet_amount = amount - platform_fee;
+        // Calculate platform fee, discounted for bettors holding or staking $WAR
+        let war_holdings = payout::add(
+            ctx.accounts.war_token_account.as_ref().map_or(0, |account| account.amount),
+            ctx.accounts.stake_account.as_ref().map_or(0, |stake| stake.staked_amount),
+        )?;
+        let discount_bps = payout::war_discount_bps(war_holdings);
+        let platform_fee = payout::discounted_fee(
+            payout::fee(amount, config.platform_fee_bps)?,
+            discount_bps,
+        )?;
+        let bet_amount = payout::sub(amount, platform_fee)?;
 
-        // Transfer SOL from user to war escrow
-        let transfer_instruction = anchor_lang::system_program::Transfer {
-            from: ctx.accounts.user.to_account_info(),
+        // Transfer tokens from user to war escrow; the fee stays there until settlement
+        // so a cancelled war can refund it
+        let transfer_instruction = TransferChecked {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.bet_mint.to_account_info(),
             to: ctx.accounts.war_escrow.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
         };
-        anchor_lang::system_program::transfer(
+        token_interface::transfer_checked(
             CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
                 transfer_instruction,
             ),
-            bet_amount,
+            amount,
+            ctx.accounts.bet_mint.decimals,
         )?;
 
-        // Transfer platform fee
-        if platform_fee > 0 {
-            let fee_transfer = anchor_lang::system_program::Transfer {
-                from: ctx.accounts.user.to_account_info(),
-                to: ctx.accounts.fee_collector.to_account_info(),
-            };
-            anchor_lang::system_program::transfer(
-                CpiContext::new(
-                    ctx.accounts.system_program.to_account_info(),
-                    fee_transfer,
-                ),
-                platform_fee,
-            )?;
+        // Open the bettor's position on their first bet in this war
+        if bet.user == Pubkey::default() {
+            bet.user = ctx.accounts.user.key();
+            bet.war = war.key();
+            bet.amounts = vec![0; war.contestants.len()];
+            bet.fee_paid = 0;
+            bet.penalty_paid = 0;
+            bet.is_claimed = false;
+            bet.bump = ctx.bumps.bet;
+            war.num_bettors += 1;
         }
-
-        // Initialize bet account
-        bet.user = ctx.accounts.user.key();
-        bet.war = war.key();
-        bet.amount = bet_amount;
-        bet.token_choice = token_choice;
+        let stake = &mut bet.amounts[contestant as usize];
+        *stake = payout::add(*stake, bet_amount)?;
+        bet.fee_paid = payout::add(bet.fee_paid, platform_fee)?;
         bet.timestamp = clock.unix_timestamp;
-        bet.is_claimed = false;
-        bet.bump = ctx.bumps.bet;
 
         // Update war totals
-        if token_choice == 0 {
-            war.total_bets_a += bet_amount;
-        } else {
-            war.total_bets_b += bet_amount;
-        }
-        war.num_bets += 1;
+        let pool = &mut war.contestants[contestant as usize].total_bets;
+        *pool = payout::add(*pool, bet_amount)?;
+        war.fee_pool = payout::add(war.fee_pool, platform_fee)?;
 
-        // Update platform stats
-        let platform_state = &mut ctx.accounts.platform_state;
-        platform_state.total_bets += 1;
-        platform_state.total_volume += amount;
+        // Update platform and per-mint stats
+        ctx.accounts.platform_state.total_bets += 1;
+        let mint_config = &mut ctx.accounts.mint_config;
+        mint_config.total_volume = payout::add(mint_config.total_volume, amount)?;
 
         emit!(BetPlaced {
             bet_id: bet.key(),
             war_id: war.key(),
             user: ctx.accounts.user.key(),
             amount: bet_amount,
-            token_choice,
+            contestant,
+            fee_paid: platform_fee,
+            discount_bps,
+        });
+
+        Ok(())
+    }
+
+    // Read-only: quote a bet of `amount` on `contestant` against the current pools, returned
+    // as Anchor return data for clients to simulate. Uses the undiscounted platform fee.
+    pub fn quote_bet(
+        ctx: Context<QuoteBet>,
+        amount: u64,
+        contestant: u8,
+    ) -> Result<payout::BetQuote> {
+        let war = &ctx.accounts.war;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!((contestant as usize) < war.contestants.len(), ErrorCode::InvalidTokenChoice);
+
+        let platform_fee = payout::fee(amount, ctx.accounts.platform_state.config.platform_fee_bps)?;
+        let pools: Vec<u64> = war.contestants.iter().map(|c| c.total_bets).collect();
+        payout::quote(
+            &pools,
+            contestant as usize,
+            payout::sub(amount, platform_fee)?,
+            war.penalty_pool,
+            0, // the fee was taken at bet time
+        )
+    }
+
+    // Withdraw `amount` of the stake on `contestant` while betting is open. The exit
+    // penalty stays in the war's pool for the winners; the platform fee is kept. Both
+    // go back to the bettor if the war is cancelled.
+    pub fn cash_out(ctx: Context<CashOut>, contestant: u8, amount: u64) -> Result<()> {
+        let war = &mut ctx.accounts.war;
+        let bet = &mut ctx.accounts.bet;
+        let clock = Clock::get()?;
+
+        require!(!ctx.accounts.platform_state.is_paused, ErrorCode::PlatformPaused);
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!((contestant as usize) < war.contestants.len(), ErrorCode::InvalidTokenChoice);
+        require!(war.status == WarStatus::Open, war.status.rejection());
+        require!(clock.unix_timestamp < war.schedule.betting_close, ErrorCode::WarEnded);
+
+        let stake = &mut bet.amounts[contestant as usize];
+        require!(amount <= *stake, ErrorCode::InsufficientStake);
+        *stake -= amount;
+
+        let penalty = payout::fee(amount, ctx.accounts.platform_state.config.exit_penalty_bps)?;
+        let withdrawn = payout::sub(amount, penalty)?;
+
+        // The bet position stays open, so num_bettors is unchanged
+        let pool = &mut war.contestants[contestant as usize].total_bets;
+        *pool = payout::sub(*pool, amount)?;
+        war.penalty_pool = payout::add(war.penalty_pool, penalty)?;
+        bet.penalty_paid = payout::add(bet.penalty_paid, penalty)?;
+
+        transfer_from_escrow(
+            war,
+            &ctx.accounts.war_escrow,
+            &ctx.accounts.bet_mint,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
+            withdrawn,
+        )?;
+
+        emit!(CashedOut {
+            bet_id: bet.key(),
+            war_id: war.key(),
+            user: ctx.accounts.user.key(),
+            contestant,
+            amount: withdrawn,
+            penalty,
         });
 
         Ok(())
     }
 
-    // Settle a war with price data
+    // Settle a war with price data; also resolves a disputed oracle result
     pub fn settle_war(
         ctx: Context<SettleWar>,
-        token_a_start_price: u64,
-        token_a_end_price: u64,
-        token_b_start_price: u64,
-        token_b_end_price: u64,
+        start_prices: Vec<u64>, // one per contestant, in order
+        end_prices: Vec<u64>,
     ) -> Result<()> {
         let war = &mut ctx.accounts.war;
         let clock = Clock::get()?;
 
-        require!(!war.is_settled, ErrorCode::WarAlreadySettled);
-        require!(clock.unix_timestamp >= war.end_time, ErrorCode::WarNotEnded);
+        require!(clock.unix_timestamp >= war.schedule.measure_end, ErrorCode::WarNotEnded);
+        war.close_betting(clock.unix_timestamp)?;
+        require!(
+            start_prices.len() == war.contestants.len() && end_prices.len() == war.contestants.len(),
+            ErrorCode::InvalidContestantCount
+        );
+        require!(start_prices.iter().all(|&price| price > 0), ErrorCode::InvalidPrice);
 
-        // Calculate percentage gains
-        let token_a_gain = if token_a_start_price > 0 {
-            ((token_a_end_price as u128 * 10000) / token_a_start_price as u128) - 10000
-        } else {
-            0
+        for (contestant, start_price) in war.contestants.iter_mut().zip(start_prices) {
+            contestant.start_price = start_price;
+        }
+        let settlement = record_end_prices(war, &end_prices)?;
+        finish_settlement(war, settlement.winner)?;
+
+        release_platform_fee(
+            war,
+            &ctx.accounts.war_escrow,
+            &ctx.accounts.bet_mint,
+            &ctx.accounts.fee_vault,
+            &mut ctx.accounts.mint_config,
+            &ctx.accounts.token_program,
+        )
+    }
+
+    // Snapshot every contestant's registered feed at measure_start; callable by anyone.
+    // The cranker's wallet is recorded and paid alongside the settlement crank.
+    pub fn snapshot_start_price(ctx: Context<SnapshotStartPrice>) -> Result<()> {
+        let war = &mut ctx.accounts.war;
+        let clock = Clock::get()?;
+
+        require!(clock.unix_timestamp >= war.schedule.measure_start, ErrorCode::WarNotStarted);
+        war.close_betting(clock.unix_timestamp)?;
+        require!(war.status == WarStatus::Locked, war.status.rejection());
+        require!(!war.start_prices_recorded(), ErrorCode::StartPricesAlreadyRecorded);
+
+        let readings = read_contestant_prices(
+            war,
+            ctx.remaining_accounts,
+            war.schedule.measure_start,
+            clock.unix_timestamp,
+        )?;
+        for (contestant, reading) in war.contestants.iter_mut().zip(&readings) {
+            contestant.start_price = reading.price;
+            contestant.exponent = reading.exponent;
+        }
+        let prices = readings.iter().map(|reading| reading.price).collect();
+
+        emit!(StartPricesSnapshotted {
+            war_id: war.key(),
+            prices,
+        });
+
+        war.snapshot_cranker = ctx.accounts.cranker.key();
+
+        Ok(())
+    }
+
+    // Propose a result from the registered feeds after measure_end; callable by anyone.
+    // The result stands once DISPUTE_WINDOW passes without a dispute.
+    pub fn settle_war_permissionless(ctx: Context<SettleWarPermissionless>) -> Result<()> {
+        let war = &mut ctx.accounts.war;
+        let clock = Clock::get()?;
+
+        require!(clock.unix_timestamp >= war.schedule.measure_end, ErrorCode::WarNotEnded);
+        war.close_betting(clock.unix_timestamp)?;
+        require!(war.status == WarStatus::Locked, war.status.rejection());
+        require!(war.start_prices_recorded(), ErrorCode::StartPricesNotRecorded);
+
+        let end_prices: Vec<u64> = read_contestant_prices(
+            war,
+            ctx.remaining_accounts,
+            war.schedule.measure_end,
+            clock.unix_timestamp,
+        )?
+        .iter()
+        .map(|reading| reading.price)
+        .collect();
+        let settlement = record_end_prices(war, &end_prices)?;
+
+        war.settle_cranker = ctx.accounts.cranker.key();
+        war.transition(WarStatus::Settling {
+            winner: settlement.winner,
+            proposed_at: clock.unix_timestamp,
+        })?;
+
+        emit!(SettlementProposed {
+            war_id: war.key(),
+            winner: settlement.winner,
+            gains: settlement.change_bps,
+        });
+
+        Ok(())
+    }
+
+    // Flag an oracle result as wrong; the settler then settles or cancels by hand
+    pub fn dispute_settlement(ctx: Context<DisputeSettlement>) -> Result<()> {
+        let war = &mut ctx.accounts.war;
+
+        if let WarStatus::Settling { proposed_at, .. } = war.status {
+            let clock = Clock::get()?;
+            require!(
+                clock.unix_timestamp < proposed_at + DISPUTE_WINDOW,
+                ErrorCode::DisputeWindowClosed
+            );
+        }
+        war.transition(WarStatus::Disputed)?;
+
+        emit!(SettlementDisputed { war_id: war.key() });
+
+        Ok(())
+    }
+
+    // Finalize an undisputed oracle result, paying both cranks and the platform; callable by anyone
+    pub fn finalize_settlement(ctx: Context<FinalizeSettlement>) -> Result<()> {
+        let war = &mut ctx.accounts.war;
+
+        let WarStatus::Settling { winner, proposed_at } = war.status else {
+            return err!(war.status.rejection());
         };
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp >= proposed_at + DISPUTE_WINDOW,
+            ErrorCode::DisputeWindowOpen
+        );
+
+        finish_settlement(war, winner)?;
+
+        let bounty =
+            payout::mul_div_floor(war.fee_pool, CRANK_BOUNTY_BPS, payout::BPS_DENOMINATOR)?;
+        let crankers = [
+            &ctx.accounts.settle_cranker_token_account,
+            &ctx.accounts.snapshot_cranker_token_account,
+        ];
+        for cranker in crankers {
+            pay_crank_bounty(
+                war,
+                &ctx.accounts.war_escrow,
+                &ctx.accounts.bet_mint,
+                cranker,
+                &ctx.accounts.token_program,
+                bounty,
+            )?;
+        }
+
+        release_platform_fee(
+            war,
+            &ctx.accounts.war_escrow,
+            &ctx.accounts.bet_mint,
+            &ctx.accounts.fee_vault,
+            &mut ctx.accounts.mint_config,
+            &ctx.accounts.token_program,
+        )
+    }
+
+    // Abort a war: the settler may cancel at any time before settlement, the creator
+    // only until measurement starts, anyone once the settlement deadline has passed.
+    // A pending or disputed result can only be cancelled by the settler.
+    pub fn cancel_war(ctx: Context<CancelWar>) -> Result<()> {
+        let war = &mut ctx.accounts.war;
+        let clock = Clock::get()?;
 
-        let token_b_gain = if token_b_start_price > 0 {
-            ((token_b_end_price as u128 * 10000) / token_b_start_price as u128) - 10000
+        let caller = ctx.accounts.caller.key();
+        let authorized = if caller == ctx.accounts.platform_state.roles.settler {
+            true
         } else {
-            0
+            match war.status {
+                WarStatus::Open | WarStatus::Locked => {
+                    let creator_window =
+                        caller == war.creator && clock.unix_timestamp < war.schedule.measure_start;
+                    let deadline_passed =
+                        clock.unix_timestamp >= war.schedule.measure_end + SETTLEMENT_DEADLINE;
+                    creator_window || deadline_passed
+                }
+                _ => false,
+            }
         };
+        require!(authorized, ErrorCode::UnauthorizedAction);
+
+        war.transition(WarStatus::Cancelled)?;
+
+        emit!(WarCancelled {
+            war_id: war.key(),
+            cancelled_by: ctx.accounts.caller.key(),
+        });
+
+        Ok(())
+    }
+
+    // Refund a bet on a cancelled war, including the platform fee and any exit penalties
+    pub fn refund_bet(ctx: Context<ClaimWinnings>) -> Result<()> {
+        let war = &mut ctx.accounts.war;
+        let bet = &mut ctx.accounts.bet;
+
+        require!(war.status == WarStatus::Cancelled, ErrorCode::WarNotCancelled);
+        require!(!bet.is_claimed, ErrorCode::AlreadyClaimed);
+        require!(bet.user == ctx.accounts.user.key(), ErrorCode::UnauthorizedClaim);
+
+        let refund = payout::add(
+            payout::add(bet.total_amount()?, bet.fee_paid)?,
+            bet.penalty_paid,
+        )?;
 
-        // Determine winner
-        war.winner = if token_a_gain > token_b_gain {
-            Some(0) // Token A wins
-        } else if token_b_gain > token_a_gain {
-            Some(1) // Token B wins
+        war.num_claimed += 1;
+        let refund = if war.num_claimed == war.num_bettors {
+            // Last refund sweeps the escrow
+            ctx.accounts.war_escrow.amount
         } else {
-            None // Tie
+            refund
         };
 
-        war.is_settled = true;
+        transfer_from_escrow(
+            war,
+            &ctx.accounts.war_escrow,
+            &ctx.accounts.bet_mint,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
+            refund,
+        )?;
+
+        bet.is_claimed = true;
 
-        emit!(WarSettled {
-            war_id: war.key(),
-            winner: war.winner,
-            token_a_gain: token_a_gain as u64,
-            token_b_gain: token_b_gain as u64,
+        emit!(BetRefunded {
+            bet_id: bet.key(),
+            user: ctx.accounts.user.key(),
+            refund,
         });
 
         Ok(())
     }
 
-    // Claim winnings from a settled war
+    // Claim winnings from a settled war for the bettor's whole position
     pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
-        let war = &ctx.accounts.war;
+        let war = &mut ctx.accounts.war;
         let bet = &mut ctx.accounts.bet;
 
-        require!(war.is_settled, ErrorCode::WarNotSettled);
+        let WarStatus::Settled { winner } = war.status else {
+            return err!(ErrorCode::WarNotSettled);
+        };
         require!(!bet.is_claimed, ErrorCode::AlreadyClaimed);
         require!(bet.user == ctx.accounts.user.key(), ErrorCode::UnauthorizedClaim);
 
-        let payout = if let Some(winner) = war.winner {
-            if bet.token_choice == winner {
-                // Calculate payout based on pool ratio
-                let winning_pool = if winner == 0 { war.total_bets_a } else { war.total_bets_b };
-                let total_pool = war.total_bets_a + war.total_bets_b;
-                
-                if winning_pool > 0 {
-                    (bet.amount as u128 * total_pool as u128 / winning_pool as u128) as u64
-                } else {
-                    bet.amount // Return original bet if no winning pool
-                }
+        // Nobody backing the winner settles like a tie, otherwise the last loser to
+        // claim would sweep the whole escrow
+        let winner = winner.filter(|&w| war.contestants[w as usize].total_bets > 0);
+        let payout = if let Some(winner) = winner {
+            let stake = bet.amounts[winner as usize];
+            if stake > 0 {
+                // Parimutuel share of every contestant's pool; stakes on losers are lost
+                let winning_pool = war.contestants[winner as usize].total_bets;
+                payout::pro_rata_share(stake, war.total_pool()?, winning_pool)?
             } else {
                 0 // Lost bet
             }
         } else {
-            bet.amount // Tie - return original bet
+            bet.total_amount()? // Tie - return every stake
+        };
+
+        war.num_claimed += 1;
+        let payout = if war.num_claimed == war.num_bettors {
+            // Last claimant sweeps rounding dust so the escrow empties
+            ctx.accounts.war_escrow.amount
+        } else {
+            payout
         };
 
         if payout > 0 {
-            // Transfer winnings from escrow to user
-            let seeds = &[
-                b"war_escrow",
-                war.key().as_ref(),
-                &[ctx.bumps.war_escrow],
-            ];
-            let signer = &[&seeds[..]];
-
-            **ctx.accounts.war_escrow.to_account_info().try_borrow_mut_lamports()? -= payout;
-            **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += payout;
+            transfer_from_escrow(
+                war,
+                &ctx.accounts.war_escrow,
+                &ctx.accounts.bet_mint,
+                &ctx.accounts.user_token_account,
+                &ctx.accounts.token_program,
+                payout,
+            )?;
         }
 
         bet.is_claimed = true;
@@ -245,6 +583,115 @@ pub mod crypto_meme_wars {
         Ok(())
     }
 
+    // Replace the platform's fee, collector, duration and creator settings
+    pub fn update_platform_config(
+        ctx: Context<UpdatePlatformConfig>,
+        config: PlatformConfig,
+    ) -> Result<()> {
+        config.validate()?;
+
+        let platform_state = &mut ctx.accounts.platform_state;
+        let old = std::mem::replace(&mut platform_state.config, config.clone());
+
+        emit!(PlatformConfigUpdated { old, new: config });
+
+        Ok(())
+    }
+
+    // Set the bet-size limits for wars staked in `bet_mint`, in its base units. A mint
+    // must be configured here before wars can be created in it.
+    pub fn update_mint_config(
+        ctx: Context<UpdateMintConfig>,
+        min_bet: u64,
+        max_bet: u64,
+    ) -> Result<()> {
+        require!(min_bet >= 1 && min_bet <= max_bet, ErrorCode::InvalidAmount);
+
+        let mint_config = &mut ctx.accounts.mint_config;
+        mint_config.bet_mint = ctx.accounts.bet_mint.key();
+        mint_config.min_bet = min_bet;
+        mint_config.max_bet = max_bet;
+        mint_config.bump = ctx.bumps.mint_config;
+
+        emit!(MintConfigUpdated {
+            bet_mint: mint_config.bet_mint,
+            min_bet,
+            max_bet,
+        });
+
+        Ok(())
+    }
+
+    // Move accumulated platform fees from a mint's fee vault to the treasury
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(amount <= ctx.accounts.fee_vault.amount, ErrorCode::InsufficientFees);
+
+        let mint_key = ctx.accounts.bet_mint.key();
+        let seeds = &[b"fee_vault", mint_key.as_ref(), &[ctx.bumps.fee_vault]];
+        let signer = &[&seeds[..]];
+
+        let transfer_instruction = TransferChecked {
+            from: ctx.accounts.fee_vault.to_account_info(),
+            mint: ctx.accounts.bet_mint.to_account_info(),
+            to: ctx.accounts.treasury.to_account_info(),
+            authority: ctx.accounts.fee_vault.to_account_info(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_instruction,
+                signer,
+            ),
+            amount,
+            ctx.accounts.bet_mint.decimals,
+        )?;
+
+        emit!(FeesWithdrawn {
+            bet_mint: mint_key,
+            treasury: ctx.accounts.treasury.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    // Assign the pauser, settler and fee manager roles
+    pub fn set_roles(ctx: Context<AdminOnly>, roles: PlatformRoles) -> Result<()> {
+        let platform_state = &mut ctx.accounts.platform_state;
+        let old = std::mem::replace(&mut platform_state.roles, roles);
+
+        emit!(RolesUpdated { old, new: roles });
+
+        Ok(())
+    }
+
+    // First step of an authority handover; the new authority must accept
+    pub fn propose_authority(ctx: Context<AdminOnly>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.platform_state.pending_authority = Some(new_authority);
+
+        emit!(AuthorityProposed {
+            authority: ctx.accounts.authority.key(),
+            pending_authority: new_authority,
+        });
+
+        Ok(())
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let platform_state = &mut ctx.accounts.platform_state;
+        let old_authority = platform_state.authority;
+        platform_state.authority = ctx.accounts.new_authority.key();
+        platform_state.pending_authority = None;
+
+        emit!(AuthorityTransferred {
+            old_authority,
+            new_authority: platform_state.authority,
+        });
+
+        Ok(())
+    }
+
     // Emergency pause function
     pub fn set_pause_state(ctx: Context<SetPauseState>, paused: bool) -> Result<()> {
         let platform_state = &mut ctx.accounts.platform_state;
@@ -256,43 +703,346 @@ pub mod crypto_meme_wars {
     }
 }
 
+// Helper functions
+// Read each contestant's registered feed, passed as remaining accounts in contestant order.
+// Once a start price is recorded, later reads must be quoted in its exponent.
+fn read_contestant_prices(
+    war: &War,
+    feeds: &[AccountInfo],
+    target_time: i64,
+    now: i64,
+) -> Result<Vec<oracle::PriceReading>> {
+    require!(feeds.len() == war.contestants.len(), ErrorCode::InvalidPriceFeed);
+    war.contestants
+        .iter()
+        .zip(feeds)
+        .map(|(contestant, feed)| {
+            require_keys_eq!(feed.key(), contestant.price_feed, ErrorCode::InvalidPriceFeed);
+            let expected_exponent = (contestant.start_price > 0).then_some(contestant.exponent);
+            oracle::read_price(feed, &contestant.feed_id, expected_exponent, target_time, now)
+        })
+        .collect()
+}
+
+fn record_end_prices(war: &mut Account<War>, end_prices: &[u64]) -> Result<payout::FieldSettlement> {
+    for (contestant, &end_price) in war.contestants.iter_mut().zip(end_prices) {
+        contestant.end_price = end_price;
+    }
+
+    // Signed percentage gains in basis points; a falling token is negative
+    war.settle_field()
+}
+
+fn finish_settlement(war: &mut Account<War>, winner: Option<u8>) -> Result<()> {
+    war.transition(WarStatus::Settled { winner })?;
+
+    let settlement = war.settle_field()?;
+
+    emit!(WarSettled {
+        war_id: war.key(),
+        winner,
+        gains: settlement.change_bps,
+    });
+
+    Ok(())
+}
+
+// Pay a cranker's token account out of the war's fee pool
+fn pay_crank_bounty<'info>(
+    war: &mut Account<'info, War>,
+    war_escrow: &InterfaceAccount<'info, TokenAccount>,
+    bet_mint: &InterfaceAccount<'info, Mint>,
+    cranker: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    bounty: u64,
+) -> Result<()> {
+    if bounty == 0 {
+        return Ok(());
+    }
+
+    transfer_from_escrow(war, war_escrow, bet_mint, cranker, token_program, bounty)?;
+    war.fee_pool = payout::sub(war.fee_pool, bounty)?;
+
+    emit!(CrankBountyPaid {
+        war_id: war.key(),
+        cranker: cranker.owner,
+        bounty,
+    });
+
+    Ok(())
+}
+
+// Forward whatever fee is left after crank bounties to the platform's fee vault
+fn release_platform_fee<'info>(
+    war: &mut Account<'info, War>,
+    war_escrow: &InterfaceAccount<'info, TokenAccount>,
+    bet_mint: &InterfaceAccount<'info, Mint>,
+    fee_vault: &InterfaceAccount<'info, TokenAccount>,
+    mint_config: &mut Account<'info, MintConfig>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    let fee = war.fee_pool;
+    if fee == 0 {
+        return Ok(());
+    }
+
+    transfer_from_escrow(war, war_escrow, bet_mint, fee_vault, token_program, fee)?;
+    war.fee_pool = 0;
+    mint_config.total_fees_collected = payout::add(mint_config.total_fees_collected, fee)?;
+
+    Ok(())
+}
+
+// Move `amount` out of the war escrow, signed by the escrow PDA
+fn transfer_from_escrow<'info>(
+    war: &Account<'info, War>,
+    war_escrow: &InterfaceAccount<'info, TokenAccount>,
+    bet_mint: &InterfaceAccount<'info, Mint>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let war_key = war.key();
+    let seeds = &[b"war_escrow", war_key.as_ref(), &[war.escrow_bump]];
+    let signer = &[&seeds[..]];
+
+    let transfer_instruction = TransferChecked {
+        from: war_escrow.to_account_info(),
+        mint: bet_mint.to_account_info(),
+        to: to.to_account_info(),
+        authority: war_escrow.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            transfer_instruction,
+            signer,
+        ),
+        amount,
+        bet_mint.decimals,
+    )
+}
+
 // Account structures
 #[account]
 pub struct PlatformState {
-    pub authority: Pubkey,
+    pub authority: Pubkey, // admin: assigns roles and hands over authority
+    pub pending_authority: Option<Pubkey>, // proposed admin awaiting accept_authority
+    pub roles: PlatformRoles,
+    pub war_mint: Pubkey, // $WAR mint whose holders earn fee discounts
+    pub config: PlatformConfig,
     pub total_bets: u64,
-    pub total_volume: u64,
-    pub platform_fee_bps: u16,
+    pub war_count: u64, // wars created so far; seeds the next war's address
     pub is_paused: bool,
     pub bump: u8,
 }
 
+// Settings and totals for one bet mint, kept apart because mints differ in decimals
+#[account]
+pub struct MintConfig {
+    pub bet_mint: Pubkey,
+    pub min_bet: u64, // bet limits are in this mint's base units
+    pub max_bet: u64,
+    pub total_volume: u64,
+    pub total_fees_collected: u64,
+    pub bump: u8,
+}
+
+impl MintConfig {
+    pub const SPACE: usize = 32 + 8 + 8 + 8 + 8 + 1;
+}
+
 #[account]
 pub struct War {
+    pub number: u64, // position in PlatformState::war_count order
     pub creator: Pubkey,
-    pub token_a_symbol: String,
-    pub token_b_symbol: String,
-    pub start_time: i64,
-    pub end_time: i64,
-    pub total_bets_a: u64,
-    pub total_bets_b: u64,
-    pub num_bets: u32,
-    pub is_settled: bool,
-    pub winner: Option<u8>, // None for tie, Some(0) for token A, Some(1) for token B
+    pub bet_mint: Pubkey, // token every bet, payout and fee is denominated in
+    pub contestants: Vec<Contestant>, // MIN_CONTESTANTS..=MAX_CONTESTANTS entries
+    pub schedule: WarSchedule,
+    pub num_bettors: u32, // Bet positions opened; the last one claimed sweeps the escrow
+    pub status: WarStatus,
     pub bump: u8,
+    pub escrow_bump: u8,
+    pub fee_pool: u64, // platform fees held in escrow until settlement; funds crank bounties
+    pub penalty_pool: u64, // cash-out penalties, shared by the winners or refunded on cancellation
+    pub num_claimed: u32,
+    pub snapshot_cranker: Pubkey, // wallets paid their bounties, to their bet-mint ATAs, at settlement
+    pub settle_cranker: Pubkey,
+}
+
+impl War {
+    pub fn start_prices_recorded(&self) -> bool {
+        self.contestants.iter().all(|c| c.start_price > 0)
+    }
+
+    pub fn total_pool(&self) -> Result<u64> {
+        self.contestants
+            .iter()
+            .try_fold(self.penalty_pool, |total, c| payout::add(total, c.total_bets))
+    }
+
+    pub fn settle_field(&self) -> Result<payout::FieldSettlement> {
+        let prices: Vec<_> = self
+            .contestants
+            .iter()
+            .map(|c| (c.start_price, c.end_price))
+            .collect();
+        payout::settle_field(&prices)
+    }
+
+    // Move to `next`, rejecting any transition the lifecycle does not allow
+    pub fn transition(&mut self, next: WarStatus) -> Result<()> {
+        require!(self.status.can_transition_to(&next), self.status.rejection());
+        self.status = next;
+        Ok(())
+    }
+
+    // Stop taking bets once betting_close has passed
+    pub fn close_betting(&mut self, now: i64) -> Result<()> {
+        require!(now >= self.schedule.betting_close, ErrorCode::WarNotEnded);
+        if self.status == WarStatus::Open {
+            self.transition(WarStatus::Locked)?;
+        }
+        Ok(())
+    }
+}
+
+// This program's error for an instruction not allowed in the war's status
+pub trait Rejection {
+    fn rejection(&self) -> ErrorCode;
+}
+
+impl Rejection for WarStatus {
+    fn rejection(&self) -> ErrorCode {
+        match self.error() {
+            StatusError::InvalidWarStatus => ErrorCode::InvalidWarStatus,
+            StatusError::SettlementPending => ErrorCode::SettlementPending,
+            StatusError::WarAlreadySettled => ErrorCode::WarAlreadySettled,
+            StatusError::WarCancelled => ErrorCode::WarCancelled,
+        }
+    }
+}
+
+// Hot keys for day-to-day operations, rotated by the admin through `set_roles`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PlatformRoles {
+    pub pauser: Pubkey,      // set_pause_state
+    pub settler: Pubkey,     // settle_war, dispute_settlement, cancel_war
+    pub fee_manager: Pubkey, // update_platform_config, update_mint_config, withdraw_fees
+}
+
+// Fee-manager-tunable platform settings, replaced wholesale by `update_platform_config`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct PlatformConfig {
+    pub platform_fee_bps: u16,
+    pub fee_collector: Pubkey, // owner of the treasury token accounts withdraw_fees pays out to
+    pub min_duration_hours: u32,
+    pub max_duration_hours: u32,
+    pub exit_penalty_bps: u16, // share of a cashed-out stake left in the war's pool
+    pub allowed_creators: Vec<Pubkey>, // empty lets anyone create wars
+}
+
+impl PlatformConfig {
+    pub const SPACE: usize = 2 + 32 + 4 + 4 + 2 + 4 + MAX_ALLOWED_CREATORS * 32;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(self.platform_fee_bps <= MAX_PLATFORM_FEE_BPS, ErrorCode::FeeTooHigh);
+        require!(self.exit_penalty_bps <= MAX_EXIT_PENALTY_BPS, ErrorCode::FeeTooHigh);
+        require!(
+            self.min_duration_hours >= 1 && self.min_duration_hours <= self.max_duration_hours,
+            ErrorCode::InvalidDuration
+        );
+        require!(
+            self.allowed_creators.len() <= MAX_ALLOWED_CREATORS,
+            ErrorCode::TooManyAllowedCreators
+        );
+        Ok(())
+    }
+
+    pub fn may_create_wars(&self, creator: &Pubkey) -> bool {
+        self.allowed_creators.is_empty() || self.allowed_creators.contains(creator)
+    }
+}
+
+// A war's timeline: bets are taken in [betting_open, betting_close), which must end
+// before prices are measured over [measure_start, measure_end]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WarSchedule {
+    pub betting_open: i64,
+    pub betting_close: i64,
+    pub measure_start: i64,
+    pub measure_end: i64,
+}
+
+impl WarSchedule {
+    pub const SPACE: usize = 4 * 8;
+
+    pub fn validate(&self, config: &PlatformConfig, now: i64) -> Result<()> {
+        require!(
+            self.betting_open < self.betting_close
+                && self.betting_close <= self.measure_start
+                && self.measure_start < self.measure_end,
+            ErrorCode::InvalidSchedule
+        );
+        require!(self.betting_close > now, ErrorCode::InvalidSchedule);
+
+        // Platform duration limits apply to the measurement window
+        let duration = self.measure_end - self.measure_start;
+        require!(
+            duration >= config.min_duration_hours as i64 * 3600
+                && duration <= config.max_duration_hours as i64 * 3600,
+            ErrorCode::InvalidDuration
+        );
+        Ok(())
+    }
+}
+
+// Token entered into a war by `create_war`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ContestantInit {
+    pub symbol: String,
+    pub price_feed: Pubkey,
+    pub feed_id: [u8; 32], // Pyth feed id every update on `price_feed` must carry
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Contestant {
+    pub symbol: String,
+    pub price_feed: Pubkey,
+    pub feed_id: [u8; 32],
+    pub exponent: i32, // set with the start price; the end price must match
+    pub start_price: u64, // 0 until snapshotted from the feed
+    pub end_price: u64,
+    pub total_bets: u64,
 }
 
+impl Contestant {
+    pub const SPACE: usize = 4 + MAX_SYMBOL_LEN + 32 + 32 + 4 + 8 + 8 + 8;
+}
+
+// A bettor's whole position in one war, built up by one or more place_bet calls
 #[account]
 pub struct Bet {
     pub user: Pubkey,
     pub war: Pubkey,
-    pub amount: u64,
-    pub token_choice: u8,
-    pub timestamp: i64,
+    pub amounts: Vec<u64>, // net stake per contestant, indexed like war.contestants
+    pub fee_paid: u64, // refunded along with `amounts` if the war is cancelled
+    pub penalty_paid: u64, // cash-out penalties, likewise refunded on cancellation
+    pub timestamp: i64, // time of the latest bet
     pub is_claimed: bool,
     pub bump: u8,
 }
 
+impl Bet {
+    pub const SPACE: usize = 32 + 32 + 4 + MAX_CONTESTANTS * 8 + 8 + 8 + 8 + 1 + 1;
+
+    pub fn total_amount(&self) -> Result<u64> {
+        self.amounts
+            .iter()
+            .try_fold(0, |total, &amount| payout::add(total, amount))
+    }
+}
+
 // Context structures
 #[derive(Accounts)]
 pub struct InitializePlatform<'info> {
@@ -301,10 +1051,12 @@ pub struct InitializePlatform<'info> {
         payer = authority,
         seeds = [b"platform_state"],
         bump,
-        space = 8 + 32 + 8 + 8 + 2 + 1 + 1
+        space = 8 + 32 + 1 + 32 + 3 * 32 + 32 + PlatformConfig::SPACE + 8 + 8 + 1 + 1
     )]
     pub platform_state: Account<'info, PlatformState>,
 
+    pub war_mint: InterfaceAccount<'info, Mint>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -316,33 +1068,69 @@ pub struct CreateWar<'info> {
     #[account(
         init,
         payer = creator,
-        seeds = [b"war", creator.key().as_ref(), &Clock::get().unwrap().unix_timestamp.to_le_bytes()],
+        seeds = [b"war", platform_state.war_count.to_le_bytes().as_ref()],
         bump,
-        space = 8 + 32 + 4 + 32 + 4 + 32 + 8 + 8 + 8 + 8 + 4 + 1 + 1 + 1
+        space = 8 + 8 + 32 + 32 + 4 + MAX_CONTESTANTS * Contestant::SPACE + WarSchedule::SPACE + 4 + WarStatus::SPACE + 1 + 1 + 8 + 8 + 4 + 32 + 32
     )]
     pub war: Account<'info, War>,
 
     #[account(
-        mut,
-        seeds = [b"platform_state"],
-        bump = platform_state.bump
+        init,
+        payer = creator,
+        seeds = [b"war_escrow", war.key().as_ref()],
+        bump,
+        token::mint = bet_mint,
+        token::authority = war_escrow,
+        token::token_program = token_program
     )]
-    pub platform_state: Account<'info, PlatformState>,
+    pub war_escrow: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
-    pub creator: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
+    // Shared by every war in this mint; collects platform fees until withdraw_fees
+    #[account(
+        init_if_needed,
+        payer = creator,
+        seeds = [b"fee_vault", bet_mint.key().as_ref()],
+        bump,
+        token::mint = bet_mint,
+        token::authority = fee_vault,
+        token::token_program = token_program
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    // SOL wars use the wrapped SOL mint
+    #[account(constraint = bet_mint::is_supported(&bet_mint.to_account_info()) @ ErrorCode::UnsupportedMint)]
+    pub bet_mint: InterfaceAccount<'info, Mint>,
+
+    // Only mints the fee manager has set bet limits for can back a war
+    #[account(
+        seeds = [b"mint_config", bet_mint.key().as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_state"],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct PlaceBet<'info> {
     #[account(
-        init,
+        init_if_needed,
         payer = user,
         seeds = [b"bet", war.key().as_ref(), user.key().as_ref()],
         bump,
-        space = 8 + 32 + 32 + 8 + 1 + 8 + 1 + 1
+        space = 8 + Bet::SPACE
     )]
     pub bet: Account<'info, Bet>,
 
@@ -359,53 +1147,361 @@ pub struct PlaceBet<'info> {
     #[account(
         mut,
         seeds = [b"war_escrow", war.key().as_ref()],
-        bump
+        bump = war.escrow_bump
     )]
-    /// CHECK: This is a PDA that holds escrowed SOL
-    pub war_escrow: AccountInfo<'info>,
+    pub war_escrow: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
-    /// CHECK: This account collects platform fees
-    pub fee_collector: AccountInfo<'info>,
+    #[account(address = war.bet_mint @ ErrorCode::InvalidMint)]
+    pub bet_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_config", bet_mint.key().as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        mut,
+        token::mint = bet_mint,
+        token::authority = user
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Optional $WAR holdings that earn a fee discount
+    #[account(
+        seeds = [b"stake", user.key().as_ref()],
+        bump = stake_account.bump,
+        seeds::program = war_token::ID,
+        constraint = stake_account.owner == user.key() @ ErrorCode::UnauthorizedAction
+    )]
+    pub stake_account: Option<Account<'info, StakeAccount>>,
+
+    #[account(
+        token::authority = user,
+        constraint = war_token_account.mint == platform_state.war_mint @ ErrorCode::InvalidMint
+    )]
+    pub war_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut)]
     pub user: Signer<'info>,
 
+    pub token_program: Interface<'info, TokenInterface>,
+
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct SettleWar<'info> {
+pub struct QuoteBet<'info> {
+    pub war: Account<'info, War>,
+
+    #[account(
+        seeds = [b"platform_state"],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+}
+
+#[derive(Accounts)]
+pub struct CashOut<'info> {
+    #[account(
+        mut,
+        seeds = [b"bet", war.key().as_ref(), user.key().as_ref()],
+        bump = bet.bump
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(mut)]
+    pub war: Account<'info, War>,
+
+    #[account(
+        seeds = [b"platform_state"],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
     #[account(
         mut,
-        constraint = war.creator == authority.key() @ ErrorCode::UnauthorizedSettlement
+        seeds = [b"war_escrow", war.key().as_ref()],
+        bump = war.escrow_bump
     )]
+    pub war_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = war.bet_mint @ ErrorCode::InvalidMint)]
+    pub bet_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = bet_mint,
+        token::authority = user
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SettleWar<'info> {
+    #[account(mut)]
     pub war: Account<'info, War>,
 
+    #[account(
+        mut,
+        seeds = [b"war_escrow", war.key().as_ref()],
+        bump = war.escrow_bump
+    )]
+    pub war_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = war.bet_mint @ ErrorCode::InvalidMint)]
+    pub bet_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"platform_state"],
+        bump = platform_state.bump,
+        constraint = platform_state.roles.settler == authority.key() @ ErrorCode::UnauthorizedSettlement
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_config", bet_mint.key().as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault", bet_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
     pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
+// Remaining accounts: each contestant's registered price feed, in contestant order
 #[derive(Accounts)]
-pub struct ClaimWinnings<'info> {
+pub struct SnapshotStartPrice<'info> {
     #[account(mut)]
-    pub bet: Account<'info, Bet>,
+    pub war: Account<'info, War>,
 
+    // Receives the snapshot bounty, to its bet-mint ATA, when the war settles
+    pub cranker: Signer<'info>,
+}
+
+// Remaining accounts: each contestant's registered price feed, in contestant order
+#[derive(Accounts)]
+pub struct SettleWarPermissionless<'info> {
+    #[account(mut)]
+    pub war: Account<'info, War>,
+
+    // Receives the settlement bounty, to its bet-mint ATA, once the result is final
+    pub cranker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeSettlement<'info> {
+    #[account(mut)]
+    pub war: Account<'info, War>,
+
+    #[account(
+        seeds = [b"platform_state"],
+        bump = platform_state.bump,
+        constraint = platform_state.roles.settler == authority.key() @ ErrorCode::UnauthorizedSettlement
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeSettlement<'info> {
+    #[account(mut)]
     pub war: Account<'info, War>,
 
     #[account(
         mut,
         seeds = [b"war_escrow", war.key().as_ref()],
+        bump = war.escrow_bump
+    )]
+    pub war_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = war.bet_mint @ ErrorCode::InvalidMint)]
+    pub bet_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_config", bet_mint.key().as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault", bet_mint.key().as_ref()],
         bump
     )]
-    /// CHECK: This is a PDA that holds escrowed SOL
-    pub war_escrow: AccountInfo<'info>,
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Only used as the owner of the bounty ATA; must be the recorded wallet
+    #[account(address = war.settle_cranker @ ErrorCode::UnauthorizedAction)]
+    pub settle_cranker: UncheckedAccount<'info>,
+
+    // Created if the cranker closed or never opened it, so finalizing can't be blocked
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = bet_mint,
+        associated_token::authority = settle_cranker,
+        associated_token::token_program = token_program
+    )]
+    pub settle_cranker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Only used as the owner of the bounty ATA; must be the recorded wallet
+    #[account(address = war.snapshot_cranker @ ErrorCode::UnauthorizedAction)]
+    pub snapshot_cranker: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = bet_mint,
+        associated_token::authority = snapshot_cranker,
+        associated_token::token_program = token_program
+    )]
+    pub snapshot_cranker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelWar<'info> {
+    #[account(mut)]
+    pub war: Account<'info, War>,
+
+    #[account(
+        seeds = [b"platform_state"],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimWinnings<'info> {
+    #[account(
+        mut,
+        seeds = [b"bet", war.key().as_ref(), user.key().as_ref()],
+        bump = bet.bump
+    )]
+    pub bet: Account<'info, Bet>,
 
     #[account(mut)]
+    pub war: Account<'info, War>,
+
+    #[account(
+        mut,
+        seeds = [b"war_escrow", war.key().as_ref()],
+        bump = war.escrow_bump
+    )]
+    pub war_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = war.bet_mint @ ErrorCode::InvalidMint)]
+    pub bet_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = bet_mint,
+        token::authority = user
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
     pub user: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-pub struct SetPauseState<'info> {
+pub struct UpdatePlatformConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_state"],
+        bump = platform_state.bump,
+        constraint = platform_state.roles.fee_manager == authority.key() @ ErrorCode::UnauthorizedAction
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMintConfig<'info> {
+    #[account(
+        seeds = [b"platform_state"],
+        bump = platform_state.bump,
+        constraint = platform_state.roles.fee_manager == authority.key() @ ErrorCode::UnauthorizedAction
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"mint_config", bet_mint.key().as_ref()],
+        bump,
+        space = 8 + MintConfig::SPACE
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(constraint = bet_mint::is_supported(&bet_mint.to_account_info()) @ ErrorCode::UnsupportedMint)]
+    pub bet_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        seeds = [b"platform_state"],
+        bump = platform_state.bump,
+        constraint = platform_state.roles.fee_manager == authority.key() @ ErrorCode::UnauthorizedAction
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault", bet_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub bet_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = bet_mint,
+        constraint = treasury.owner == platform_state.config.fee_collector @ ErrorCode::UnauthorizedAction
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct AdminOnly<'info> {
     #[account(
         mut,
         seeds = [b"platform_state"],
@@ -417,6 +1513,32 @@ pub struct SetPauseState<'info> {
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_state"],
+        bump = platform_state.bump,
+        constraint = platform_state.pending_authority == Some(new_authority.key()) @ ErrorCode::UnauthorizedAction
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPauseState<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_state"],
+        bump = platform_state.bump,
+        constraint = platform_state.roles.pauser == authority.key() @ ErrorCode::UnauthorizedAction
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
+    pub authority: Signer<'info>,
+}
+
 // Events
 #[event]
 pub struct PlatformInitialized {
@@ -427,9 +1549,10 @@ pub struct PlatformInitialized {
 #[event]
 pub struct WarCreated {
     pub war_id: Pubkey,
-    pub token_a: String,
-    pub token_b: String,
-    pub end_time: i64,
+    pub number: u64,
+    pub bet_mint: Pubkey,
+    pub symbols: Vec<String>,
+    pub schedule: WarSchedule,
 }
 
 #[event]
@@ -438,15 +1561,64 @@ pub struct BetPlaced {
     pub war_id: Pubkey,
     pub user: Pubkey,
     pub amount: u64,
-    pub token_choice: u8,
+    pub contestant: u8,
+    pub fee_paid: u64,
+    pub discount_bps: u16, // $WAR holder discount applied to the platform fee
+}
+
+#[event]
+pub struct CashedOut {
+    pub bet_id: Pubkey,
+    pub war_id: Pubkey,
+    pub user: Pubkey,
+    pub contestant: u8,
+    pub amount: u64, // paid out, after the penalty
+    pub penalty: u64,
 }
 
 #[event]
 pub struct WarSettled {
     pub war_id: Pubkey,
     pub winner: Option<u8>,
-    pub token_a_gain: u64,
-    pub token_b_gain: u64,
+    pub gains: Vec<i64>, // basis points per contestant, negative when the price fell
+}
+
+#[event]
+pub struct SettlementProposed {
+    pub war_id: Pubkey,
+    pub winner: Option<u8>,
+    pub gains: Vec<i64>,
+}
+
+#[event]
+pub struct SettlementDisputed {
+    pub war_id: Pubkey,
+}
+
+#[event]
+pub struct StartPricesSnapshotted {
+    pub war_id: Pubkey,
+    pub prices: Vec<u64>, // per contestant
+}
+
+#[event]
+pub struct CrankBountyPaid {
+    pub war_id: Pubkey,
+    pub cranker: Pubkey,
+    pub bounty: u64,
+}
+
+#[event]
+pub struct WarCancelled {
+    pub war_id: Pubkey,
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct BetRefunded {
+    pub bet_id: Pubkey,
+    pub user: Pubkey,
+    pub refund: u64,
 }
 
 #[event]
@@ -456,6 +1628,44 @@ pub struct WinningsClaimed {
     pub payout: u64,
 }
 
+#[event]
+pub struct PlatformConfigUpdated {
+    pub old: PlatformConfig,
+    pub new: PlatformConfig,
+}
+
+#[event]
+pub struct MintConfigUpdated {
+    pub bet_mint: Pubkey,
+    pub min_bet: u64,
+    pub max_bet: u64,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub bet_mint: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RolesUpdated {
+    pub old: PlatformRoles,
+    pub new: PlatformRoles,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
 #[event]
 pub struct PauseStateChanged {
     pub paused: bool,
@@ -474,7 +1684,7 @@ pub enum ErrorCode {
     InvalidTokenChoice,
     #[msg("War has already been settled")]
     WarAlreadySettled,
-    #[msg("War has ended, no more bets allowed")]
+    #[msg("Betting has closed for this war")]
     WarEnded,
     #[msg("War has not ended yet")]
     WarNotEnded,
@@ -488,4 +1698,62 @@ pub enum ErrorCode {
     UnauthorizedSettlement,
     #[msg("Unauthorized action")]
     UnauthorizedAction,
+    #[msg("War has not started yet")]
+    WarNotStarted,
+    #[msg("Price feed account is not the registered oracle feed")]
+    InvalidPriceFeed,
+    #[msg("Price feed reported a non-positive price")]
+    InvalidPrice,
+    #[msg("Price feed is stale")]
+    StalePrice,
+    #[msg("Price was not published within the snapshot window")]
+    PriceOutsideWindow,
+    #[msg("Price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Start prices have already been recorded")]
+    StartPricesAlreadyRecorded,
+    #[msg("Start prices have not been recorded yet")]
+    StartPricesNotRecorded,
+    #[msg("War has been cancelled")]
+    WarCancelled,
+    #[msg("War has not been cancelled")]
+    WarNotCancelled,
+    #[msg("Instruction is not allowed in the war's current status")]
+    InvalidWarStatus,
+    #[msg("War has a settlement pending")]
+    SettlementPending,
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+    #[msg("War needs between 2 and 8 contestants")]
+    InvalidContestantCount,
+    #[msg("Token symbol is too long")]
+    SymbolTooLong,
+    #[msg("Contestants must use distinct price feeds")]
+    DuplicateContestant,
+    #[msg("Token account does not match the war's betting mint")]
+    InvalidMint,
+    #[msg("Platform fee exceeds the maximum")]
+    FeeTooHigh,
+    #[msg("Too many allowed creators")]
+    TooManyAllowedCreators,
+    #[msg("Creator is not allowed to create wars")]
+    CreatorNotAllowed,
+    #[msg("Bet size is outside the platform limits")]
+    BetOutOfRange,
+    #[msg("Fee vault holds less than the requested amount")]
+    InsufficientFees,
+    #[msg("Betting has not opened for this war yet")]
+    BettingNotOpen,
+    #[msg("War schedule must open and close betting before measuring prices")]
+    InvalidSchedule,
+    #[msg("Cash-out exceeds the stake on that contestant")]
+    InsufficientStake,
+    #[msg("Price update is for a different feed than the one registered")]
+    FeedIdMismatch,
+    #[msg("End price exponent differs from the start price exponent")]
+    ExponentChanged,
+    #[msg("Mint has a transfer fee, permanent delegate or transfer hook")]
+    UnsupportedMint,
 }
\ No newline at end of file
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+pub use price_feed::{
+    PriceError, PriceFeedMessage, PriceReading, PriceUpdateV2, VerificationLevel,
+    MAX_CONFIDENCE_BPS, MAX_PRICE_AGE, MAX_SNAPSHOT_DELAY, PRICE_FEED_OWNER,
+};
+
+use crate::ErrorCode;
+
+// Read a price through the shared `price_feed` checks, reported as this program's errors
+pub fn read_price(
+    feed: &AccountInfo,
+    feed_id: &[u8; 32],
+    expected_exponent: Option<i32>,
+    target_time: i64,
+    now: i64,
+) -> Result<PriceReading> {
+    price_feed::read_price(feed, feed_id, expected_exponent, target_time, now).map_err(|error| {
+        error!(match error {
+            PriceError::InvalidPriceFeed => ErrorCode::InvalidPriceFeed,
+            PriceError::FeedIdMismatch => ErrorCode::FeedIdMismatch,
+            PriceError::ExponentChanged => ErrorCode::ExponentChanged,
+            PriceError::InvalidPrice => ErrorCode::InvalidPrice,
+            PriceError::StalePrice => ErrorCode::StalePrice,
+            PriceError::PriceOutsideWindow => ErrorCode::PriceOutsideWindow,
+            PriceError::PriceConfidenceTooWide => ErrorCode::PriceConfidenceTooWide,
+        })
+    })
+}
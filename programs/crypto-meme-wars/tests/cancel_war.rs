@@ -0,0 +1,79 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use crypto_meme_wars::{instruction, ErrorCode, War, WarStatus, SETTLEMENT_DEADLINE};
+use common::{
+    error_code, platform_address, platform_state, process, set_clock, TestAccount, WarKeys,
+    PLATFORM_SPACE, SCHEDULE, WAR_SPACE,
+};
+
+const CREATOR: Pubkey = Pubkey::new_from_array([1; 32]);
+const SETTLER: Pubkey = Pubkey::new_from_array([2; 32]);
+const STRANGER: Pubkey = Pubkey::new_from_array([3; 32]);
+
+fn cancel(status: WarStatus, caller: Pubkey) -> std::result::Result<(), ProgramError> {
+    let keys = WarKeys::unique();
+    let war = War {
+        status,
+        creator: CREATOR,
+        ..keys.war_state(2)
+    };
+    let mut accounts = [
+        TestAccount::program_owned(keys.war, &war, WAR_SPACE),
+        TestAccount::program_owned(platform_address().0, &platform_state(SETTLER), PLATFORM_SPACE),
+        TestAccount::system(caller, 0).signed(),
+    ];
+    let result = process(instruction::CancelWar {}, &mut accounts);
+    if result.is_ok() {
+        assert_eq!(accounts[0].state::<War>().status, WarStatus::Cancelled);
+    }
+    result
+}
+
+fn settling() -> WarStatus {
+    WarStatus::Settling {
+        winner: Some(0),
+        proposed_at: SCHEDULE.measure_end,
+    }
+}
+
+#[test]
+fn creator_can_cancel_only_before_measurement() {
+    set_clock(SCHEDULE.measure_start - 1);
+    cancel(WarStatus::Open, CREATOR).unwrap();
+    cancel(WarStatus::Locked, CREATOR).unwrap();
+
+    set_clock(SCHEDULE.measure_start);
+    assert_eq!(
+        cancel(WarStatus::Locked, CREATOR),
+        error_code(ErrorCode::UnauthorizedAction)
+    );
+}
+
+#[test]
+fn only_settler_can_cancel_a_pending_or_disputed_result() {
+    // Even past the settlement deadline, neither the creator nor anyone else may
+    // throw away a proposed result
+    set_clock(SCHEDULE.measure_end + SETTLEMENT_DEADLINE);
+    for status in [settling(), WarStatus::Disputed] {
+        for caller in [CREATOR, STRANGER] {
+            assert_eq!(
+                cancel(status, caller),
+                error_code(ErrorCode::UnauthorizedAction)
+            );
+        }
+        cancel(status, SETTLER).unwrap();
+    }
+}
+
+#[test]
+fn anyone_can_cancel_an_unsettled_war_after_the_deadline() {
+    set_clock(SCHEDULE.measure_end + SETTLEMENT_DEADLINE - 1);
+    assert_eq!(
+        cancel(WarStatus::Locked, STRANGER),
+        error_code(ErrorCode::UnauthorizedAction)
+    );
+
+    set_clock(SCHEDULE.measure_end + SETTLEMENT_DEADLINE);
+    cancel(WarStatus::Locked, STRANGER).unwrap();
+}
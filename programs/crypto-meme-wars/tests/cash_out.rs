@@ -0,0 +1,163 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{
+    bet_account, error_code, platform_address, platform_state, process, set_clock, TestAccount,
+    WarKeys, PLATFORM_SPACE, SCHEDULE, WAR_SPACE,
+};
+use crypto_meme_wars::{instruction, Bet, ErrorCode, PlatformState, War, WarStatus};
+
+const STAKE: u64 = 1_000_000;
+const FEE: u64 = 30_000;
+
+// Accounts for `user` cashing out of a war where they staked STAKE on contestant 0
+// and another bettor staked STAKE on contestant 1
+fn cash_out_accounts(
+    keys: &WarKeys,
+    user: TestAccount,
+    platform: PlatformState,
+) -> [TestAccount; 8] {
+    let mut war = War {
+        num_bettors: 2,
+        fee_pool: 2 * FEE,
+        ..keys.war_state(2)
+    };
+    war.contestants[0].total_bets = STAKE;
+    war.contestants[1].total_bets = STAKE;
+    [
+        bet_account(keys.war, user.key, vec![STAKE, 0], FEE),
+        TestAccount::program_owned(keys.war, &war, WAR_SPACE),
+        TestAccount::program_owned(platform_address().0, &platform, PLATFORM_SPACE),
+        keys.escrow_account(2 * (STAKE + FEE)),
+        keys.mint_account(),
+        TestAccount::token_account(Pubkey::new_unique(), keys.mint, user.key, 0),
+        user,
+        TestAccount::token_program(),
+    ]
+}
+
+fn cash_out(accounts: &mut [TestAccount], amount: u64) -> std::result::Result<(), ProgramError> {
+    process(
+        instruction::CashOut {
+            contestant: 0,
+            amount,
+        },
+        accounts,
+    )
+}
+
+#[test]
+fn partial_cash_out_moves_the_penalty_into_the_pool() {
+    set_clock(SCHEDULE.betting_open + 100);
+    let keys = WarKeys::unique();
+    let platform = platform_state(Pubkey::new_unique());
+    let mut accounts = cash_out_accounts(&keys, TestAccount::signer(0), platform);
+
+    // 5% exit penalty on 400_000
+    cash_out(&mut accounts, 400_000).unwrap();
+
+    let bet: Bet = accounts[0].state();
+    assert_eq!(bet.amounts, vec![600_000, 0]);
+    assert_eq!(bet.penalty_paid, 20_000);
+    let war: War = accounts[1].state();
+    assert_eq!(war.contestants[0].total_bets, 600_000);
+    assert_eq!(war.penalty_pool, 20_000);
+    assert_eq!(war.fee_pool, 2 * FEE);
+    assert_eq!(war.num_bettors, 2);
+    // The penalty stays in escrow with the rest of the pool
+    assert_eq!(war.total_pool().unwrap(), 2 * STAKE - 380_000);
+    assert_eq!(accounts[5].token_amount(), 380_000);
+    assert_eq!(accounts[3].token_amount(), 2 * (STAKE + FEE) - 380_000);
+}
+
+#[test]
+fn cash_out_is_rejected_once_betting_closes() {
+    let keys = WarKeys::unique();
+    let platform = platform_state(Pubkey::new_unique());
+    let mut accounts = cash_out_accounts(&keys, TestAccount::signer(0), platform);
+
+    set_clock(SCHEDULE.betting_close);
+    assert_eq!(cash_out(&mut accounts, 1), error_code(ErrorCode::WarEnded));
+}
+
+#[test]
+fn cash_out_is_limited_to_the_stake_on_the_contestant() {
+    set_clock(SCHEDULE.betting_open + 100);
+    let keys = WarKeys::unique();
+    let platform = platform_state(Pubkey::new_unique());
+    let mut accounts = cash_out_accounts(&keys, TestAccount::signer(0), platform);
+
+    assert_eq!(
+        cash_out(&mut accounts, STAKE + 1),
+        error_code(ErrorCode::InsufficientStake)
+    );
+    cash_out(&mut accounts, STAKE).unwrap();
+    assert_eq!(
+        cash_out(&mut accounts, 1),
+        error_code(ErrorCode::InsufficientStake)
+    );
+}
+
+#[test]
+fn cash_out_is_rejected_while_the_platform_is_paused() {
+    set_clock(SCHEDULE.betting_open + 100);
+    let keys = WarKeys::unique();
+    let platform = PlatformState {
+        is_paused: true,
+        ..platform_state(Pubkey::new_unique())
+    };
+    let mut accounts = cash_out_accounts(&keys, TestAccount::signer(0), platform);
+
+    assert_eq!(
+        cash_out(&mut accounts, 1),
+        error_code(ErrorCode::PlatformPaused)
+    );
+}
+
+#[test]
+fn cancelled_war_refunds_exit_penalties_to_whoever_paid_them() {
+    set_clock(SCHEDULE.betting_open + 100);
+    let keys = WarKeys::unique();
+    let platform = platform_state(Pubkey::new_unique());
+    let leaver = TestAccount::signer(0);
+    let leaver_key = leaver.key;
+    let mut accounts = cash_out_accounts(&keys, leaver, platform);
+    cash_out(&mut accounts, 400_000).unwrap();
+
+    let war = War {
+        status: WarStatus::Cancelled,
+        ..accounts[1].state()
+    };
+    let [leaver_bet, _, _, mut escrow, ..] = accounts;
+    let mut war = TestAccount::program_owned(keys.war, &war, WAR_SPACE);
+
+    // The leaver refunds first, so the last refunder's sweep can't be what pays them
+    let stayer = TestAccount::signer(0);
+    let stayer_bet = bet_account(keys.war, stayer.key, vec![0, STAKE], FEE);
+    let refunds = [
+        (
+            leaver_bet,
+            TestAccount::system(leaver_key, 0).signed(),
+            600_000 + FEE + 20_000,
+        ),
+        (stayer_bet, stayer, STAKE + FEE),
+    ];
+    for (bet, user, refund) in refunds {
+        let mut accounts = [
+            bet,
+            war,
+            escrow,
+            keys.mint_account(),
+            TestAccount::token_account(Pubkey::new_unique(), keys.mint, user.key, 0),
+            user,
+            TestAccount::token_program(),
+        ];
+        process(instruction::RefundBet {}, &mut accounts).unwrap();
+        assert_eq!(accounts[4].token_amount(), refund);
+
+        let [_, w, e, ..] = accounts;
+        (war, escrow) = (w, e);
+    }
+
+    assert_eq!(escrow.token_amount(), 0);
+}
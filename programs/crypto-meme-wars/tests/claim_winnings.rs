@@ -0,0 +1,55 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use crypto_meme_wars::{instruction, Bet, War, WarStatus};
+use common::{bet_address, process, set_clock, TestAccount, WarKeys, BET_SPACE, WAR_SPACE};
+
+#[test]
+fn unbacked_winner_refunds_every_stake() {
+    set_clock(3_000);
+    let keys = WarKeys::unique();
+    let mut state = War {
+        status: WarStatus::Settled { winner: Some(1) },
+        num_bettors: 2,
+        ..keys.war_state(3)
+    };
+    state.contestants[0].total_bets = 1_000_000;
+    state.contestants[2].total_bets = 3_000_000;
+    let mut war = TestAccount::program_owned(keys.war, &state, WAR_SPACE);
+    let mut escrow = keys.escrow_account(4_000_000);
+
+    // Nobody backed contestant 1, so the first claimant must not lose their stake
+    // to whoever claims last
+    for amounts in [vec![1_000_000, 0, 0], vec![0, 0, 3_000_000]] {
+        let user = TestAccount::signer(0);
+        let (bet_key, bet_bump) = bet_address(&keys.war, &user.key);
+        let stake: u64 = amounts.iter().sum();
+        let bet = Bet {
+            user: user.key,
+            war: keys.war,
+            amounts,
+            fee_paid: 0,
+            penalty_paid: 0,
+            timestamp: 1_200,
+            is_claimed: false,
+            bump: bet_bump,
+        };
+        let mut accounts = [
+            TestAccount::program_owned(bet_key, &bet, BET_SPACE),
+            war,
+            escrow,
+            keys.mint_account(),
+            TestAccount::token_account(Pubkey::new_unique(), keys.mint, user.key, 0),
+            user,
+            TestAccount::token_program(),
+        ];
+        process(instruction::ClaimWinnings {}, &mut accounts).unwrap();
+        assert_eq!(accounts[4].token_amount(), stake);
+
+        let [_, w, e, ..] = accounts;
+        (war, escrow) = (w, e);
+    }
+
+    assert_eq!(escrow.token_amount(), 0);
+    assert_eq!(war.state::<War>().num_claimed, 2);
+}
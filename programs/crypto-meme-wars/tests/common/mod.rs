@@ -0,0 +1,204 @@
+//! `crypto_meme_wars` accounts and addresses on top of the shared
+//! `test_harness`, which drives the program with locally crafted accounts.
+
+#![allow(dead_code, unused_imports)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::InstructionData;
+use crypto_meme_wars::{
+    Bet, Contestant, MintConfig, PlatformConfig, PlatformRoles, PlatformState, War, WarSchedule, WarStatus,
+    MAX_CONTESTANTS,
+};
+
+pub use test_harness::{
+    ata_address, error_code, events, price_message, return_data, set_clock, token_account_space,
+    TestAccount,
+};
+
+pub const WAR_SPACE: usize = 8 + 8 + 32 + 32 + 4 + MAX_CONTESTANTS * Contestant::SPACE
+    + WarSchedule::SPACE + 4 + WarStatus::SPACE + 1 + 1 + 8 + 8 + 4 + 32 + 32;
+pub const BET_SPACE: usize = 8 + Bet::SPACE;
+pub const PLATFORM_SPACE: usize =
+    8 + 32 + 1 + 32 + 3 * 32 + 32 + PlatformConfig::SPACE + 8 + 8 + 1 + 1;
+pub const MINT_CONFIG_SPACE: usize = 8 + MintConfig::SPACE;
+
+pub const SCHEDULE: WarSchedule = WarSchedule {
+    betting_open: 1_000,
+    betting_close: 1_500,
+    measure_start: 1_500,
+    measure_end: 2_000,
+};
+
+pub fn platform_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"platform_state"], &crypto_meme_wars::ID)
+}
+
+pub fn escrow_address(war: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"war_escrow", war.as_ref()], &crypto_meme_wars::ID)
+}
+
+pub fn fee_vault_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"fee_vault", mint.as_ref()], &crypto_meme_wars::ID)
+}
+
+pub fn mint_config_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"mint_config", mint.as_ref()], &crypto_meme_wars::ID)
+}
+
+pub fn bet_address(war: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"bet", war.as_ref(), user.as_ref()], &crypto_meme_wars::ID)
+}
+
+/// Addresses of a war, its escrow and its betting mint.
+pub struct WarKeys {
+    pub war: Pubkey,
+    pub escrow: Pubkey,
+    pub escrow_bump: u8,
+    pub mint: Pubkey,
+}
+
+impl WarKeys {
+    pub fn unique() -> Self {
+        let war = Pubkey::new_unique();
+        let (escrow, escrow_bump) = escrow_address(&war);
+        Self {
+            war,
+            escrow,
+            escrow_bump,
+            mint: Pubkey::new_unique(),
+        }
+    }
+
+    pub fn war_state(&self, contestants: usize) -> War {
+        war_state(self.mint, self.escrow_bump, contestants)
+    }
+
+    pub fn escrow_account(&self, amount: u64) -> TestAccount {
+        TestAccount::token_account(self.escrow, self.mint, self.escrow, amount)
+    }
+
+    pub fn mint_account(&self) -> TestAccount {
+        TestAccount::mint(self.mint, 6)
+    }
+
+    pub fn mint_config_account(&self) -> TestAccount {
+        mint_config_account(self.mint, 1, u64::MAX)
+    }
+}
+
+/// A configured bet mint accepting bets in `[min_bet, max_bet]`.
+pub fn mint_config_account(mint: Pubkey, min_bet: u64, max_bet: u64) -> TestAccount {
+    let (key, bump) = mint_config_address(&mint);
+    let config = MintConfig {
+        bet_mint: mint,
+        min_bet,
+        max_bet,
+        total_volume: 0,
+        total_fees_collected: 0,
+        bump,
+    };
+    TestAccount::program_owned(key, &config, MINT_CONFIG_SPACE)
+}
+
+pub fn fee_vault_account(mint: Pubkey, amount: u64) -> TestAccount {
+    let vault = fee_vault_address(&mint).0;
+    TestAccount::token_account(vault, mint, vault, amount)
+}
+
+/// Accounts for `finalize_settlement`; the crankers' bounty ATAs don't exist yet.
+pub fn finalize_accounts(keys: &WarKeys, war: &War, escrow_amount: u64) -> [TestAccount; 13] {
+    [
+        TestAccount::program_owned(keys.war, war, WAR_SPACE),
+        keys.escrow_account(escrow_amount),
+        keys.mint_account(),
+        keys.mint_config_account(),
+        fee_vault_account(keys.mint, 0),
+        TestAccount::system(war.settle_cranker, 0),
+        TestAccount::uninitialized(ata_address(&war.settle_cranker, &keys.mint), 165),
+        TestAccount::system(war.snapshot_cranker, 0),
+        TestAccount::uninitialized(ata_address(&war.snapshot_cranker, &keys.mint), 165),
+        TestAccount::signer(1_000_000_000),
+        TestAccount::token_program(),
+        TestAccount::associated_token_program(),
+        TestAccount::system_program(),
+    ]
+}
+
+pub fn platform_state(authority: Pubkey) -> PlatformState {
+    PlatformState {
+        authority,
+        pending_authority: None,
+        roles: PlatformRoles {
+            pauser: authority,
+            settler: authority,
+            fee_manager: authority,
+        },
+        war_mint: Pubkey::new_unique(),
+        config: PlatformConfig {
+            platform_fee_bps: 300,
+            fee_collector: authority,
+            min_duration_hours: 1,
+            max_duration_hours: 168,
+            exit_penalty_bps: 500,
+            allowed_creators: Vec::new(),
+        },
+        total_bets: 0,
+        war_count: 0,
+        is_paused: false,
+        bump: platform_address().1,
+    }
+}
+
+/// `user`'s open position in `war`, staking `amounts` per contestant.
+pub fn bet_account(war: Pubkey, user: Pubkey, amounts: Vec<u64>, fee_paid: u64) -> TestAccount {
+    let (key, bump) = bet_address(&war, &user);
+    let bet = Bet {
+        user,
+        war,
+        amounts,
+        fee_paid,
+        penalty_paid: 0,
+        timestamp: SCHEDULE.betting_open,
+        is_claimed: false,
+        bump,
+    };
+    TestAccount::program_owned(key, &bet, BET_SPACE)
+}
+
+pub fn war_state(bet_mint: Pubkey, escrow_bump: u8, contestants: usize) -> War {
+    War {
+        number: 0,
+        creator: Pubkey::new_unique(),
+        bet_mint,
+        contestants: (0..contestants)
+            .map(|i| {
+                let price_feed = Pubkey::new_unique();
+                Contestant {
+                    symbol: format!("MEME{i}"),
+                    price_feed,
+                    feed_id: price_feed.to_bytes(),
+                    exponent: 0,
+                    start_price: 0,
+                    end_price: 0,
+                    total_bets: 0,
+                }
+            })
+            .collect(),
+        schedule: SCHEDULE,
+        num_bettors: 0,
+        status: WarStatus::Open,
+        bump: 0,
+        escrow_bump,
+        fee_pool: 0,
+        penalty_pool: 0,
+        num_claimed: 0,
+        snapshot_cranker: Pubkey::default(),
+        settle_cranker: Pubkey::default(),
+    }
+}
+
+/// Runs one instruction through the `crypto_meme_wars` entrypoint.
+pub fn process<I: InstructionData>(ix: I, accounts: &mut [TestAccount]) -> ProgramResult {
+    test_harness::process(crypto_meme_wars::entry, &crypto_meme_wars::ID, ix, accounts)
+}
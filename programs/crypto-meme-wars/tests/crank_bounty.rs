@@ -0,0 +1,42 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use crypto_meme_wars::{instruction, MintConfig, War, WarStatus, DISPUTE_WINDOW};
+use common::{finalize_accounts, process, set_clock, WarKeys};
+
+const PROPOSED_AT: i64 = 2_030;
+
+#[test]
+fn bounties_are_paid_to_the_crankers_wallets() {
+    set_clock(PROPOSED_AT + DISPUTE_WINDOW);
+    let keys = WarKeys::unique();
+    let settle_cranker = Pubkey::new_unique();
+    let snapshot_cranker = Pubkey::new_unique();
+    let mut war = War {
+        status: WarStatus::Settling {
+            winner: Some(0),
+            proposed_at: PROPOSED_AT,
+        },
+        settle_cranker,
+        snapshot_cranker,
+        fee_pool: 100_000,
+        ..keys.war_state(2)
+    };
+    for (contestant, end_price) in war.contestants.iter_mut().zip([1_200_000, 900_000]) {
+        contestant.start_price = 1_000_000;
+        contestant.end_price = end_price;
+    }
+
+    // Neither cranker holds a bet-mint account; finalize creates them
+    let mut accounts = finalize_accounts(&keys, &war, 100_000);
+    process(instruction::FinalizeSettlement {}, &mut accounts).unwrap();
+
+    let war: War = accounts[0].state();
+    assert_eq!(war.status, WarStatus::Settled { winner: Some(0) });
+    assert_eq!(war.fee_pool, 0);
+    assert_eq!(accounts[6].token_amount(), 5_000);
+    assert_eq!(accounts[8].token_amount(), 5_000);
+    assert_eq!(accounts[4].token_amount(), 90_000);
+    assert_eq!(accounts[3].state::<MintConfig>().total_fees_collected, 90_000);
+    assert_eq!(accounts[1].token_amount(), 0);
+}
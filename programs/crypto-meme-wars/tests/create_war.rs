@@ -0,0 +1,169 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use common::{
+    error_code, escrow_address, fee_vault_address, mint_config_account, platform_address,
+    platform_state, process, set_clock, token_account_space, TestAccount, PLATFORM_SPACE, SCHEDULE,
+    WAR_SPACE,
+};
+use crypto_meme_wars::{instruction, ContestantInit, ErrorCode, War, WarSchedule, WarStatus};
+
+// SCHEDULE with a measurement window long enough for the platform's 1-hour minimum
+const HOUR_LONG: WarSchedule = WarSchedule {
+    measure_end: SCHEDULE.measure_start + 3_600,
+    ..SCHEDULE
+};
+
+fn contestants() -> Vec<ContestantInit> {
+    (0..2)
+        .map(|i| {
+            let price_feed = Pubkey::new_unique();
+            ContestantInit {
+                symbol: format!("MEME{i}"),
+                price_feed,
+                feed_id: price_feed.to_bytes(),
+            }
+        })
+        .collect()
+}
+
+// Accounts for the platform's first war, in `mint` under `token_program`
+fn create_accounts(
+    mint: TestAccount,
+    token_program: TestAccount,
+    token_space: usize,
+) -> [TestAccount; 9] {
+    let war = Pubkey::find_program_address(&[b"war", &0u64.to_le_bytes()], &crypto_meme_wars::ID).0;
+    let authority = Pubkey::new_unique();
+    [
+        TestAccount::uninitialized(war, WAR_SPACE),
+        TestAccount::uninitialized(escrow_address(&war).0, token_space),
+        TestAccount::uninitialized(fee_vault_address(&mint.key).0, token_space),
+        mint.clone(),
+        mint_config_account(mint.key, 1, u64::MAX),
+        TestAccount::program_owned(
+            platform_address().0,
+            &platform_state(authority),
+            PLATFORM_SPACE,
+        ),
+        TestAccount::signer(10_000_000_000),
+        token_program,
+        TestAccount::system_program(),
+    ]
+}
+
+fn create_war(
+    accounts: &mut [TestAccount],
+    schedule: WarSchedule,
+) -> std::result::Result<(), ProgramError> {
+    process(
+        instruction::CreateWar {
+            contestants: contestants(),
+            schedule,
+        },
+        accounts,
+    )
+}
+
+fn spl_accounts() -> [TestAccount; 9] {
+    create_accounts(
+        TestAccount::mint(Pubkey::new_unique(), 6),
+        TestAccount::token_program(),
+        165,
+    )
+}
+
+#[test]
+fn war_can_be_scheduled_ahead_of_betting() {
+    set_clock(HOUR_LONG.betting_open - 500);
+    let mut accounts = spl_accounts();
+    create_war(&mut accounts, HOUR_LONG).unwrap();
+
+    let war: War = accounts[0].state();
+    assert_eq!(war.schedule, HOUR_LONG);
+    assert_eq!(war.status, WarStatus::Open);
+}
+
+#[test]
+fn windows_out_of_order_are_rejected() {
+    set_clock(900);
+    let schedules = [
+        // Betting closes before it opens
+        WarSchedule {
+            betting_close: HOUR_LONG.betting_open,
+            ..HOUR_LONG
+        },
+        // Bets still taken once measurement starts
+        WarSchedule {
+            betting_close: HOUR_LONG.measure_start + 1,
+            ..HOUR_LONG
+        },
+        // Measurement ends before it starts
+        WarSchedule {
+            measure_end: HOUR_LONG.measure_start,
+            ..HOUR_LONG
+        },
+    ];
+    for schedule in schedules {
+        assert_eq!(
+            create_war(&mut spl_accounts(), schedule),
+            error_code(ErrorCode::InvalidSchedule),
+            "{schedule:?}"
+        );
+    }
+
+    // Betting already over
+    set_clock(HOUR_LONG.betting_close);
+    assert_eq!(
+        create_war(&mut spl_accounts(), HOUR_LONG),
+        error_code(ErrorCode::InvalidSchedule)
+    );
+}
+
+#[test]
+fn measurement_window_must_fit_the_platform_limits() {
+    set_clock(900);
+    // SCHEDULE measures for under the 1-hour minimum
+    assert_eq!(
+        create_war(&mut spl_accounts(), SCHEDULE),
+        error_code(ErrorCode::InvalidDuration)
+    );
+}
+
+#[test]
+fn token_2022_mint_without_risky_extensions_is_accepted() {
+    set_clock(900);
+    let extensions = [ExtensionType::MintCloseAuthority];
+    let mint = TestAccount::mint_2022(Pubkey::new_unique(), 6, &extensions);
+    let mut accounts = create_accounts(
+        mint,
+        TestAccount::token_2022_program(),
+        token_account_space(&extensions),
+    );
+    create_war(&mut accounts, HOUR_LONG).unwrap();
+    assert_eq!(accounts[0].state::<War>().bet_mint, accounts[3].key);
+}
+
+#[test]
+fn mints_that_can_shortchange_or_drain_the_escrow_are_rejected() {
+    set_clock(900);
+    // A 0 bps fee is still rejected: its authority can raise it mid-war
+    for extension in [
+        ExtensionType::TransferFeeConfig,
+        ExtensionType::PermanentDelegate,
+        ExtensionType::TransferHook,
+    ] {
+        let mint = TestAccount::mint_2022(Pubkey::new_unique(), 6, &[extension]);
+        let mut accounts = create_accounts(
+            mint,
+            TestAccount::token_2022_program(),
+            token_account_space(&[extension]),
+        );
+        assert_eq!(
+            create_war(&mut accounts, HOUR_LONG),
+            error_code(ErrorCode::UnsupportedMint),
+            "{extension:?}"
+        );
+    }
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{
+    bet_address, error_code, mint_config_account, mint_config_address, platform_address,
+    platform_state, process, set_clock, TestAccount, WarKeys, BET_SPACE, MINT_CONFIG_SPACE,
+    PLATFORM_SPACE, WAR_SPACE,
+};
+use crypto_meme_wars::{instruction, ErrorCode, MintConfig};
+
+fn update_accounts(fee_manager: Pubkey, signer: TestAccount, mint: Pubkey) -> [TestAccount; 5] {
+    [
+        TestAccount::program_owned(
+            platform_address().0,
+            &platform_state(fee_manager),
+            PLATFORM_SPACE,
+        ),
+        TestAccount::uninitialized(mint_config_address(&mint).0, MINT_CONFIG_SPACE),
+        TestAccount::mint(mint, 6),
+        signer,
+        TestAccount::system_program(),
+    ]
+}
+
+#[test]
+fn fee_manager_sets_limits_per_mint() {
+    let fee_manager = TestAccount::signer(1_000_000_000);
+    let mint = Pubkey::new_unique();
+    let mut accounts = update_accounts(fee_manager.key, fee_manager, mint);
+
+    assert_eq!(
+        process(
+            instruction::UpdateMintConfig {
+                min_bet: 10,
+                max_bet: 9,
+            },
+            &mut accounts
+        ),
+        error_code(ErrorCode::InvalidAmount)
+    );
+    process(
+        instruction::UpdateMintConfig {
+            min_bet: 1_000_000,
+            max_bet: 500_000_000,
+        },
+        &mut accounts,
+    )
+    .unwrap();
+
+    let config: MintConfig = accounts[1].state();
+    assert_eq!(config.bet_mint, mint);
+    assert_eq!((config.min_bet, config.max_bet), (1_000_000, 500_000_000));
+    assert_eq!(config.bump, mint_config_address(&mint).1);
+}
+
+#[test]
+fn only_the_fee_manager_sets_mint_limits() {
+    let mut accounts = update_accounts(
+        Pubkey::new_unique(),
+        TestAccount::signer(1_000_000_000),
+        Pubkey::new_unique(),
+    );
+    assert_eq!(
+        process(
+            instruction::UpdateMintConfig {
+                min_bet: 1,
+                max_bet: 100,
+            },
+            &mut accounts
+        ),
+        error_code(ErrorCode::UnauthorizedAction)
+    );
+}
+
+// 1 USDC-like token is 10^6 base units and 1 SOL is 10^9; each mint keeps its own limits
+#[test]
+fn bets_are_bounded_by_their_own_mints_limits() {
+    set_clock(1_200);
+    for (min_bet, max_bet) in [(1_000_000, 100_000_000), (1_000_000_000, 100_000_000_000)] {
+        let keys = WarKeys::unique();
+        let user = TestAccount::signer(1_000_000_000);
+        let mut accounts = [
+            TestAccount::uninitialized(bet_address(&keys.war, &user.key).0, BET_SPACE),
+            TestAccount::program_owned(keys.war, &keys.war_state(2), WAR_SPACE),
+            TestAccount::program_owned(
+                platform_address().0,
+                &platform_state(Pubkey::new_unique()),
+                PLATFORM_SPACE,
+            ),
+            keys.escrow_account(0),
+            keys.mint_account(),
+            mint_config_account(keys.mint, min_bet, max_bet),
+            TestAccount::token_account(Pubkey::new_unique(), keys.mint, user.key, u64::MAX / 2),
+            TestAccount::none(crypto_meme_wars::ID),
+            TestAccount::none(crypto_meme_wars::ID),
+            user,
+            TestAccount::token_program(),
+            TestAccount::system_program(),
+        ];
+
+        for amount in [min_bet - 1, max_bet + 1] {
+            assert_eq!(
+                process(
+                    instruction::PlaceBet {
+                        amount,
+                        contestant: 0,
+                    },
+                    &mut accounts
+                ),
+                error_code(ErrorCode::BetOutOfRange),
+                "{amount}"
+            );
+        }
+        process(
+            instruction::PlaceBet {
+                amount: max_bet,
+                contestant: 0,
+            },
+            &mut accounts,
+        )
+        .unwrap();
+        assert_eq!(accounts[5].state::<MintConfig>().total_volume, max_bet);
+    }
+}
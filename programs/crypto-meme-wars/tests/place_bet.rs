@@ -0,0 +1,161 @@
+mod common;
+
+use anchor_lang::error::ErrorCode as AnchorError;
+use anchor_lang::prelude::*;
+use common::{
+    bet_address, error_code, platform_address, platform_state, process, set_clock, TestAccount,
+    WarKeys, BET_SPACE, PLATFORM_SPACE, SCHEDULE, WAR_SPACE,
+};
+use crypto_meme_wars::{instruction, Bet, ErrorCode, PlatformState};
+use war_token::StakeAccount;
+
+const WAR_MINT: Pubkey = Pubkey::new_from_array([7; 32]);
+const WAR: u64 = 1_000_000; // one $WAR in base units
+const AMOUNT: u64 = 1_000_000;
+
+fn stake_account(user: Pubkey, staked_amount: u64) -> TestAccount {
+    let (key, bump) = Pubkey::find_program_address(&[b"stake", user.as_ref()], &war_token::ID);
+    let stake = StakeAccount {
+        owner: user,
+        staked_amount,
+        last_stake_time: 0,
+        pending_rewards: 0,
+        reward_debt: 0,
+        lockup_end: 0,
+        boost_bps: war_token::BASE_BOOST_BPS,
+        unstaking_amount: 0,
+        unstake_available_at: 0,
+        snapshots: Vec::new(),
+        pruned_until: None,
+        bump,
+    };
+    TestAccount::program_owned(key, &stake, 512)
+}
+
+// Places an AMOUNT bet at `now` with the given optional $WAR accounts and returns the
+// fee charged
+fn bet_at(
+    now: i64,
+    user: TestAccount,
+    stake: Option<TestAccount>,
+    war_tokens: Option<TestAccount>,
+) -> std::result::Result<u64, ProgramError> {
+    set_clock(now);
+    let keys = WarKeys::unique();
+    let platform = PlatformState {
+        war_mint: WAR_MINT,
+        ..platform_state(Pubkey::new_unique())
+    };
+    let mut accounts = [
+        TestAccount::uninitialized(bet_address(&keys.war, &user.key).0, BET_SPACE),
+        TestAccount::program_owned(keys.war, &keys.war_state(2), WAR_SPACE),
+        TestAccount::program_owned(platform_address().0, &platform, PLATFORM_SPACE),
+        keys.escrow_account(0),
+        keys.mint_account(),
+        keys.mint_config_account(),
+        TestAccount::token_account(Pubkey::new_unique(), keys.mint, user.key, AMOUNT),
+        stake.unwrap_or(TestAccount::none(crypto_meme_wars::ID)),
+        war_tokens.unwrap_or(TestAccount::none(crypto_meme_wars::ID)),
+        user,
+        TestAccount::token_program(),
+        TestAccount::system_program(),
+    ];
+    process(
+        instruction::PlaceBet {
+            amount: AMOUNT,
+            contestant: 0,
+        },
+        &mut accounts,
+    )?;
+    let bet: Bet = accounts[0].state();
+    assert_eq!(bet.amounts[0] + bet.fee_paid, AMOUNT);
+    Ok(bet.fee_paid)
+}
+
+fn bet_with(
+    user: TestAccount,
+    stake: Option<TestAccount>,
+    war_tokens: Option<TestAccount>,
+) -> std::result::Result<u64, ProgramError> {
+    bet_at(SCHEDULE.betting_open, user, stake, war_tokens)
+}
+
+fn war_tokens(owner: Pubkey, amount: u64) -> TestAccount {
+    TestAccount::token_account(Pubkey::new_unique(), WAR_MINT, owner, amount)
+}
+
+#[test]
+fn bets_are_taken_only_while_betting_is_open() {
+    let bet = |now| bet_at(now, TestAccount::signer(1_000_000_000), None, None).map(|_| ());
+    assert_eq!(
+        bet(SCHEDULE.betting_open - 1),
+        error_code(ErrorCode::BettingNotOpen)
+    );
+    bet(SCHEDULE.betting_open).unwrap();
+    bet(SCHEDULE.betting_close - 1).unwrap();
+    assert_eq!(bet(SCHEDULE.betting_close), error_code(ErrorCode::WarEnded));
+}
+
+#[test]
+fn bettors_without_war_pay_the_full_fee() {
+    let user = TestAccount::signer(1_000_000_000);
+    assert_eq!(bet_with(user, None, None), Ok(30_000));
+}
+
+#[test]
+fn wallet_war_earns_a_discount() {
+    // 10K $WAR: 15% off the 3% fee
+    let user = TestAccount::signer(1_000_000_000);
+    let wallet = war_tokens(user.key, 10_000 * WAR);
+    assert_eq!(bet_with(user, None, Some(wallet)), Ok(25_500));
+}
+
+#[test]
+fn staked_war_earns_a_discount() {
+    // 100K $WAR staked: 30% off
+    let user = TestAccount::signer(1_000_000_000);
+    let stake = stake_account(user.key, 100_000 * WAR);
+    assert_eq!(bet_with(user, Some(stake), None), Ok(21_000));
+}
+
+#[test]
+fn wallet_and_staked_war_count_together() {
+    // 600 + 500 $WAR reaches the 1K tier: 5% off
+    let user = TestAccount::signer(1_000_000_000);
+    let stake = stake_account(user.key, 500 * WAR);
+    let wallet = war_tokens(user.key, 600 * WAR);
+    assert_eq!(bet_with(user, Some(stake), Some(wallet)), Ok(28_500));
+}
+
+#[test]
+fn war_token_account_must_hold_war_for_the_bettor() {
+    let user = TestAccount::signer(1_000_000_000);
+    let other_mint = TestAccount::token_account(
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        user.key,
+        1_000_000 * WAR,
+    );
+    assert_eq!(
+        bet_with(user, None, Some(other_mint)).map(|_| ()),
+        error_code(ErrorCode::InvalidMint)
+    );
+
+    // Someone else's $WAR doesn't discount this bettor's fee
+    let user = TestAccount::signer(1_000_000_000);
+    let borrowed = war_tokens(Pubkey::new_unique(), 1_000_000 * WAR);
+    assert_eq!(
+        bet_with(user, None, Some(borrowed)).map(|_| ()),
+        error_code(AnchorError::ConstraintTokenOwner)
+    );
+}
+
+#[test]
+fn stake_account_must_be_the_bettors() {
+    let user = TestAccount::signer(1_000_000_000);
+    let borrowed = stake_account(Pubkey::new_unique(), 1_000_000 * WAR);
+    assert_eq!(
+        bet_with(user, Some(borrowed), None).map(|_| ()),
+        error_code(AnchorError::ConstraintSeeds)
+    );
+}
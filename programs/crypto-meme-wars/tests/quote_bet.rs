@@ -0,0 +1,131 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{
+    bet_address, error_code, platform_address, platform_state, process, return_data, set_clock,
+    TestAccount, WarKeys, BET_SPACE, PLATFORM_SPACE, SCHEDULE, WAR_SPACE,
+};
+use crypto_meme_wars::{instruction, Bet, ErrorCode, War, WarStatus};
+use payout::BetQuote;
+
+fn platform_account() -> TestAccount {
+    TestAccount::program_owned(
+        platform_address().0,
+        &platform_state(Pubkey::new_unique()),
+        PLATFORM_SPACE,
+    )
+}
+
+// Three earlier bettors: 1_000_000 on contestant 0, 3_000_000 on contestant 2, and
+// 100_000 of cash-out penalties
+fn war_account(keys: &WarKeys) -> TestAccount {
+    let mut war = War {
+        num_bettors: 3,
+        penalty_pool: 100_000,
+        ..keys.war_state(3)
+    };
+    war.contestants[0].total_bets = 1_000_000;
+    war.contestants[2].total_bets = 3_000_000;
+    TestAccount::program_owned(keys.war, &war, WAR_SPACE)
+}
+
+#[test]
+fn quote_matches_the_settled_payout() {
+    set_clock(SCHEDULE.betting_open);
+    let keys = WarKeys::unique();
+    let mut accounts = [war_account(&keys), platform_account()];
+
+    process(
+        instruction::QuoteBet {
+            amount: 1_000_000,
+            contestant: 0,
+        },
+        &mut accounts,
+    )
+    .unwrap();
+    let quote: BetQuote = return_data();
+    // 970_000 after the 3% fee, sharing 5_070_000 with the 1_000_000 already on contestant 0
+    assert_eq!(
+        quote,
+        BetQuote {
+            stake: 970_000,
+            projected_payout: 2_496_395,
+            odds_bps: 25_736,
+        }
+    );
+
+    // Place the quoted bet, settle with contestant 0 winning and claim
+    let [war, platform] = accounts;
+    let user = TestAccount::signer(1_000_000_000);
+    let user_key = user.key;
+    let mut accounts = [
+        TestAccount::uninitialized(bet_address(&keys.war, &user_key).0, BET_SPACE),
+        war,
+        platform,
+        keys.escrow_account(4_100_000),
+        keys.mint_account(),
+        keys.mint_config_account(),
+        TestAccount::token_account(Pubkey::new_unique(), keys.mint, user_key, 1_000_000),
+        TestAccount::none(crypto_meme_wars::ID),
+        TestAccount::none(crypto_meme_wars::ID),
+        user,
+        TestAccount::token_program(),
+        TestAccount::system_program(),
+    ];
+    process(
+        instruction::PlaceBet {
+            amount: 1_000_000,
+            contestant: 0,
+        },
+        &mut accounts,
+    )
+    .unwrap();
+
+    // Settlement moves the fee to the fee vault
+    let [bet, war, _, _, mint, _, _, _, _, user, token_program, _] = accounts;
+    let settled = War {
+        status: WarStatus::Settled { winner: Some(0) },
+        fee_pool: 0,
+        ..war.state()
+    };
+    let mut accounts = [
+        bet,
+        TestAccount::program_owned(keys.war, &settled, WAR_SPACE),
+        keys.escrow_account(5_070_000),
+        mint,
+        TestAccount::token_account(Pubkey::new_unique(), keys.mint, user_key, 0),
+        user,
+        token_program,
+    ];
+    process(instruction::ClaimWinnings {}, &mut accounts).unwrap();
+
+    assert!(accounts[0].state::<Bet>().is_claimed);
+    assert_eq!(accounts[4].token_amount(), quote.projected_payout);
+}
+
+#[test]
+fn quote_rejects_empty_and_unknown_bets() {
+    let keys = WarKeys::unique();
+    let mut accounts = [war_account(&keys), platform_account()];
+
+    assert_eq!(
+        process(
+            instruction::QuoteBet {
+                amount: 0,
+                contestant: 0,
+            },
+            &mut accounts,
+        ),
+        error_code(ErrorCode::InvalidAmount)
+    );
+    assert_eq!(
+        process(
+            instruction::QuoteBet {
+                amount: 1_000_000,
+                contestant: 3,
+            },
+            &mut accounts,
+        ),
+        error_code(ErrorCode::InvalidTokenChoice)
+    );
+}
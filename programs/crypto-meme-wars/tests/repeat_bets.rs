@@ -0,0 +1,113 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{
+    bet_address, platform_address, platform_state, process, set_clock, TestAccount, WarKeys,
+    BET_SPACE, PLATFORM_SPACE, SCHEDULE, WAR_SPACE,
+};
+use crypto_meme_wars::{instruction, Bet, War, WarStatus};
+
+// Accounts for one wallet betting repeatedly on a 2-contestant war
+fn place_accounts(keys: &WarKeys) -> [TestAccount; 12] {
+    let user = TestAccount::signer(1_000_000_000);
+    [
+        TestAccount::uninitialized(bet_address(&keys.war, &user.key).0, BET_SPACE),
+        TestAccount::program_owned(keys.war, &keys.war_state(2), WAR_SPACE),
+        TestAccount::program_owned(
+            platform_address().0,
+            &platform_state(Pubkey::new_unique()),
+            PLATFORM_SPACE,
+        ),
+        keys.escrow_account(0),
+        keys.mint_account(),
+        keys.mint_config_account(),
+        TestAccount::token_account(Pubkey::new_unique(), keys.mint, user.key, 10_000_000),
+        TestAccount::none(crypto_meme_wars::ID),
+        TestAccount::none(crypto_meme_wars::ID),
+        user,
+        TestAccount::token_program(),
+        TestAccount::system_program(),
+    ]
+}
+
+// Bets 1_000_000 on contestant 0 twice and 2_000_000 on contestant 1; the 3% fee
+// leaves stakes of 1_940_000 and 1_940_000
+fn place_three_bets(accounts: &mut [TestAccount]) {
+    set_clock(SCHEDULE.betting_open);
+    for (contestant, amount) in [(0, 1_000_000), (0, 1_000_000), (1, 2_000_000)] {
+        process(instruction::PlaceBet { amount, contestant }, accounts).unwrap();
+    }
+}
+
+// Places the bets, then runs `ix` for the bettor once the war's status is `status`,
+// returning what they were paid. A second bettor who staked 1_940_000 on contestant 0
+// (for 2_000_000 with the fee) hasn't collected yet, so nothing is swept.
+fn collect<I: anchor_lang::InstructionData>(status: WarStatus, ix: I) -> u64 {
+    let keys = WarKeys::unique();
+    let mut accounts = place_accounts(&keys);
+    place_three_bets(&mut accounts);
+
+    let [bet, war, _, escrow, mint, _, _, _, _, user, token_program, _] = accounts;
+    let mut war = War {
+        status,
+        num_bettors: 2,
+        fee_pool: 0,
+        ..war.state()
+    };
+    war.contestants[0].total_bets += 1_940_000;
+    let mut escrow_amount = escrow.token_amount() + 2_000_000;
+    if status != WarStatus::Cancelled {
+        // Settlement forwarded both bettors' fees to the fee vault
+        escrow_amount -= 120_000 + 60_000;
+    }
+    let user_key = user.key;
+    let mut accounts = [
+        bet,
+        TestAccount::program_owned(keys.war, &war, WAR_SPACE),
+        keys.escrow_account(escrow_amount),
+        mint,
+        TestAccount::token_account(Pubkey::new_unique(), keys.mint, user_key, 0),
+        user,
+        token_program,
+    ];
+    process(ix, &mut accounts).unwrap();
+    assert_eq!(
+        accounts[2].token_amount(),
+        escrow_amount - accounts[4].token_amount()
+    );
+    accounts[4].token_amount()
+}
+
+#[test]
+fn repeat_bets_add_to_one_position() {
+    let keys = WarKeys::unique();
+    let mut accounts = place_accounts(&keys);
+    place_three_bets(&mut accounts);
+
+    let bet: Bet = accounts[0].state();
+    assert_eq!(bet.amounts, vec![1_940_000, 1_940_000]);
+    assert_eq!(bet.fee_paid, 120_000);
+    let war: War = accounts[1].state();
+    assert_eq!(war.num_bettors, 1);
+    assert_eq!(war.contestants[0].total_bets, 1_940_000);
+    assert_eq!(war.contestants[1].total_bets, 1_940_000);
+    assert_eq!(war.fee_pool, 120_000);
+    assert_eq!(accounts[3].token_amount(), 4_000_000);
+}
+
+#[test]
+fn claim_pays_the_combined_position() {
+    // Only the 1_940_000 on the winner counts: its share of the 5_820_000 pool, of
+    // which 3_880_000 backed the winner
+    let paid = collect(
+        WarStatus::Settled { winner: Some(0) },
+        instruction::ClaimWinnings {},
+    );
+    assert_eq!(paid, 2_910_000);
+}
+
+#[test]
+fn refund_returns_every_stake_and_fee() {
+    let paid = collect(WarStatus::Cancelled, instruction::RefundBet {});
+    assert_eq!(paid, 4_000_000);
+}
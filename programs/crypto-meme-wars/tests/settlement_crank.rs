@@ -0,0 +1,113 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{error_code, price_message, process, set_clock, TestAccount, WarKeys, WAR_SPACE};
+use crypto_meme_wars::oracle::PriceFeedMessage;
+use crypto_meme_wars::{instruction, ErrorCode, War, WarStatus};
+
+const PRICE: u64 = 100_000_000;
+
+// War, cranker and then each contestant's feed as remaining accounts
+fn crank_accounts(keys: &WarKeys, war: &War, feeds: Vec<TestAccount>) -> Vec<TestAccount> {
+    let mut accounts = vec![
+        TestAccount::program_owned(keys.war, war, WAR_SPACE),
+        TestAccount::signer(0),
+    ];
+    accounts.extend(feeds);
+    accounts
+}
+
+fn feeds(war: &War, prices: &[u64], publish_time: i64) -> Vec<TestAccount> {
+    war.contestants
+        .iter()
+        .zip(prices)
+        .map(|(contestant, &price)| {
+            TestAccount::price_feed(contestant.price_feed, price, 0, publish_time)
+        })
+        .collect()
+}
+
+fn with_start_prices(mut war: War) -> War {
+    war.status = WarStatus::Locked;
+    for contestant in &mut war.contestants {
+        contestant.start_price = PRICE;
+        contestant.exponent = -8;
+    }
+    war
+}
+
+#[test]
+fn anyone_can_snapshot_and_settle_from_feeds() {
+    let keys = WarKeys::unique();
+    let war = keys.war_state(3);
+
+    set_clock(1_510);
+    let mut accounts = crank_accounts(&keys, &war, feeds(&war, &[PRICE; 3], 1_505));
+    process(instruction::SnapshotStartPrice {}, &mut accounts).unwrap();
+    let war: War = accounts[0].state();
+    assert_eq!(war.status, WarStatus::Locked);
+    for contestant in &war.contestants {
+        assert_eq!((contestant.start_price, contestant.exponent), (PRICE, -8));
+    }
+
+    // Contestant 2 gains the most
+    set_clock(2_030);
+    let end_prices = [PRICE * 101 / 100, PRICE * 90 / 100, PRICE * 105 / 100];
+    let mut accounts = crank_accounts(&keys, &war, feeds(&war, &end_prices, 2_020));
+    process(instruction::SettleWarPermissionless {}, &mut accounts).unwrap();
+    let war: War = accounts[0].state();
+    assert_eq!(
+        war.status,
+        WarStatus::Settling {
+            winner: Some(2),
+            proposed_at: 2_030,
+        }
+    );
+    assert_eq!(war.contestants[2].end_price, PRICE * 105 / 100);
+}
+
+#[test]
+fn rejects_update_for_another_feed() {
+    let keys = WarKeys::unique();
+    let war = keys.war_state(2);
+
+    // The registered account, rewritten by its write authority with another feed's price
+    set_clock(1_510);
+    let mut feeds = feeds(&war, &[PRICE; 2], 1_505);
+    let key = war.contestants[1].price_feed;
+    feeds[1] = TestAccount::price_update(
+        key,
+        PriceFeedMessage {
+            feed_id: Pubkey::new_unique().to_bytes(),
+            ..price_message(key, PRICE, 0, 1_505)
+        },
+    );
+    let mut accounts = crank_accounts(&keys, &war, feeds);
+    assert_eq!(
+        process(instruction::SnapshotStartPrice {}, &mut accounts),
+        error_code(ErrorCode::FeedIdMismatch)
+    );
+}
+
+#[test]
+fn rejects_end_price_in_another_exponent() {
+    let keys = WarKeys::unique();
+    let war = with_start_prices(keys.war_state(2));
+
+    // 1.0 at exponent -6 reads as 100x the start price at exponent -8
+    set_clock(2_030);
+    let mut feeds = feeds(&war, &[PRICE; 2], 2_020);
+    let key = war.contestants[0].price_feed;
+    feeds[0] = TestAccount::price_update(
+        key,
+        PriceFeedMessage {
+            exponent: -6,
+            ..price_message(key, 1_000_000, 0, 2_020)
+        },
+    );
+    let mut accounts = crank_accounts(&keys, &war, feeds);
+    assert_eq!(
+        process(instruction::SettleWarPermissionless {}, &mut accounts),
+        error_code(ErrorCode::ExponentChanged)
+    );
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{
+    error_code, events, platform_address, platform_state, process, TestAccount, PLATFORM_SPACE,
+};
+use crypto_meme_wars::{
+    instruction, ErrorCode, PlatformConfig, PlatformConfigUpdated, PlatformRoles, PlatformState,
+    MAX_ALLOWED_CREATORS, MAX_EXIT_PENALTY_BPS, MAX_PLATFORM_FEE_BPS,
+};
+
+const ADMIN: Pubkey = Pubkey::new_from_array([1; 32]);
+const FEE_MANAGER: Pubkey = Pubkey::new_from_array([2; 32]);
+
+fn platform() -> PlatformState {
+    PlatformState {
+        roles: PlatformRoles {
+            fee_manager: FEE_MANAGER,
+            ..platform_state(ADMIN).roles
+        },
+        ..platform_state(ADMIN)
+    }
+}
+
+fn update(caller: Pubkey, config: PlatformConfig) -> std::result::Result<(), ProgramError> {
+    let mut accounts = [
+        TestAccount::program_owned(platform_address().0, &platform(), PLATFORM_SPACE),
+        TestAccount::system(caller, 0).signed(),
+    ];
+    process(
+        instruction::UpdatePlatformConfig {
+            config: config.clone(),
+        },
+        &mut accounts,
+    )?;
+    assert_eq!(accounts[0].state::<PlatformState>().config, config);
+    Ok(())
+}
+
+#[test]
+fn fee_manager_replaces_the_config_and_logs_the_change() {
+    let config = PlatformConfig {
+        platform_fee_bps: MAX_PLATFORM_FEE_BPS,
+        fee_collector: Pubkey::new_unique(),
+        min_duration_hours: 2,
+        max_duration_hours: 2,
+        exit_penalty_bps: MAX_EXIT_PENALTY_BPS,
+        allowed_creators: vec![Pubkey::new_unique(); MAX_ALLOWED_CREATORS],
+    };
+    update(FEE_MANAGER, config.clone()).unwrap();
+
+    let [logged] = &events::<PlatformConfigUpdated>()[..] else {
+        panic!("expected one PlatformConfigUpdated event");
+    };
+    assert_eq!(logged.old, platform().config);
+    assert_eq!(logged.new, config);
+}
+
+#[test]
+fn only_the_fee_manager_updates_the_config() {
+    // Not even the admin, who assigns the fee manager role
+    for caller in [ADMIN, Pubkey::new_unique()] {
+        assert_eq!(
+            update(caller, platform().config),
+            error_code(ErrorCode::UnauthorizedAction)
+        );
+    }
+}
+
+#[test]
+fn out_of_bounds_configs_are_rejected() {
+    let valid = platform().config;
+    let cases = [
+        (
+            PlatformConfig {
+                platform_fee_bps: MAX_PLATFORM_FEE_BPS + 1,
+                ..valid.clone()
+            },
+            ErrorCode::FeeTooHigh,
+        ),
+        (
+            PlatformConfig {
+                exit_penalty_bps: MAX_EXIT_PENALTY_BPS + 1,
+                ..valid.clone()
+            },
+            ErrorCode::FeeTooHigh,
+        ),
+        (
+            PlatformConfig {
+                min_duration_hours: 0,
+                ..valid.clone()
+            },
+            ErrorCode::InvalidDuration,
+        ),
+        (
+            PlatformConfig {
+                min_duration_hours: 3,
+                max_duration_hours: 2,
+                ..valid.clone()
+            },
+            ErrorCode::InvalidDuration,
+        ),
+        (
+            PlatformConfig {
+                allowed_creators: vec![Pubkey::new_unique(); MAX_ALLOWED_CREATORS + 1],
+                ..valid.clone()
+            },
+            ErrorCode::TooManyAllowedCreators,
+        ),
+    ];
+    for (config, error) in cases {
+        assert_eq!(
+            update(FEE_MANAGER, config.clone()),
+            error_code(error),
+            "{config:?}"
+        );
+    }
+}
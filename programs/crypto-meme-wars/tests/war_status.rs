@@ -0,0 +1,216 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use crypto_meme_wars::{instruction, Bet, ErrorCode, Rejection, War, WarStatus, DISPUTE_WINDOW};
+use common::{
+    bet_address, error_code, platform_address, platform_state, process,
+    set_clock, TestAccount, WarKeys, BET_SPACE, PLATFORM_SPACE, WAR_SPACE,
+};
+
+const PROPOSED_AT: i64 = 2_030;
+
+fn all_statuses() -> [WarStatus; 6] {
+    [
+        WarStatus::Open,
+        WarStatus::Locked,
+        WarStatus::Settling {
+            winner: Some(0),
+            proposed_at: PROPOSED_AT,
+        },
+        WarStatus::Disputed,
+        WarStatus::Settled { winner: Some(1) },
+        WarStatus::Cancelled,
+    ]
+}
+
+fn legal(from: &WarStatus, to: &WarStatus) -> bool {
+    use WarStatus::*;
+    match from {
+        Open => matches!(to, Locked | Cancelled),
+        Locked => matches!(to, Settling { .. } | Settled { .. } | Cancelled),
+        Settling { .. } => matches!(to, Settled { .. } | Disputed | Cancelled),
+        Disputed => matches!(to, Settled { .. } | Cancelled),
+        Settled { .. } | Cancelled => false,
+    }
+}
+
+#[test]
+fn every_illegal_transition_is_rejected() {
+    for from in all_statuses() {
+        for to in all_statuses() {
+            let mut war = War {
+                status: from,
+                ..WarKeys::unique().war_state(2)
+            };
+            let result = war.transition(to).map_err(ProgramError::from);
+            if legal(&from, &to) {
+                assert_eq!(result, Ok(()), "{from:?} -> {to:?}");
+                assert_eq!(war.status, to);
+            } else {
+                assert_eq!(result, error_code(from.rejection()), "{from:?} -> {to:?}");
+                assert_eq!(war.status, from);
+            }
+        }
+    }
+}
+
+#[test]
+fn bets_are_only_taken_while_open() {
+    set_clock(1_200);
+    for status in all_statuses().into_iter().skip(1) {
+        let keys = WarKeys::unique();
+        let user = TestAccount::signer(1_000_000_000);
+        let (bet_key, _) = bet_address(&keys.war, &user.key);
+        let war = War {
+            status,
+            ..keys.war_state(2)
+        };
+        let mut accounts = [
+            TestAccount::uninitialized(bet_key, BET_SPACE),
+            TestAccount::program_owned(keys.war, &war, WAR_SPACE),
+            TestAccount::program_owned(
+                platform_address().0,
+                &platform_state(Pubkey::new_unique()),
+                PLATFORM_SPACE,
+            ),
+            keys.escrow_account(0),
+            keys.mint_account(),
+            keys.mint_config_account(),
+            TestAccount::token_account(Pubkey::new_unique(), keys.mint, user.key, 1_000_000),
+            TestAccount::none(crypto_meme_wars::ID),
+            TestAccount::none(crypto_meme_wars::ID),
+            user,
+            TestAccount::token_program(),
+            TestAccount::system_program(),
+        ];
+        assert_eq!(
+            process(
+                instruction::PlaceBet {
+                    amount: 1_000_000,
+                    contestant: 0,
+                },
+                &mut accounts
+            ),
+            error_code(status.rejection()),
+            "{status:?}"
+        );
+    }
+}
+
+fn settler_accounts(status: WarStatus) -> ([TestAccount; 3], Pubkey) {
+    let keys = WarKeys::unique();
+    let settler = Pubkey::new_unique();
+    let war = War {
+        status,
+        ..keys.war_state(2)
+    };
+    (
+        [
+            TestAccount::program_owned(keys.war, &war, WAR_SPACE),
+            TestAccount::program_owned(platform_address().0, &platform_state(settler), PLATFORM_SPACE),
+            TestAccount::signer(0),
+        ],
+        settler,
+    )
+}
+
+#[test]
+fn settler_can_dispute_within_window_only() {
+    let (mut accounts, settler) = settler_accounts(all_statuses()[2]);
+
+    set_clock(PROPOSED_AT + 10);
+    assert_eq!(
+        process(instruction::DisputeSettlement {}, &mut accounts),
+        error_code(ErrorCode::UnauthorizedSettlement)
+    );
+
+    accounts[2] = TestAccount::system(settler, 0).signed();
+    set_clock(PROPOSED_AT + DISPUTE_WINDOW);
+    assert_eq!(
+        process(instruction::DisputeSettlement {}, &mut accounts),
+        error_code(ErrorCode::DisputeWindowClosed)
+    );
+
+    set_clock(PROPOSED_AT + DISPUTE_WINDOW - 1);
+    process(instruction::DisputeSettlement {}, &mut accounts).unwrap();
+    assert_eq!(accounts[0].state::<War>().status, WarStatus::Disputed);
+}
+
+fn finalize_accounts(status: WarStatus) -> [TestAccount; 13] {
+    let keys = WarKeys::unique();
+    let mut war = War {
+        status,
+        settle_cranker: Pubkey::new_unique(),
+        snapshot_cranker: Pubkey::new_unique(),
+        ..keys.war_state(2)
+    };
+    for (contestant, end_price) in war.contestants.iter_mut().zip([1_200_000, 900_000]) {
+        contestant.start_price = 1_000_000;
+        contestant.end_price = end_price;
+    }
+    common::finalize_accounts(&keys, &war, 0)
+}
+
+#[test]
+fn finalize_waits_for_dispute_window() {
+    set_clock(PROPOSED_AT + DISPUTE_WINDOW - 1);
+    let mut accounts = finalize_accounts(all_statuses()[2]);
+    assert_eq!(
+        process(instruction::FinalizeSettlement {}, &mut accounts),
+        error_code(ErrorCode::DisputeWindowOpen)
+    );
+
+    set_clock(PROPOSED_AT + DISPUTE_WINDOW);
+    process(instruction::FinalizeSettlement {}, &mut accounts).unwrap();
+    assert_eq!(
+        accounts[0].state::<War>().status,
+        WarStatus::Settled { winner: Some(0) }
+    );
+}
+
+#[test]
+fn disputed_result_cannot_be_finalized() {
+    set_clock(PROPOSED_AT + DISPUTE_WINDOW);
+    let mut accounts = finalize_accounts(WarStatus::Disputed);
+    assert_eq!(
+        process(instruction::FinalizeSettlement {}, &mut accounts),
+        error_code(ErrorCode::SettlementPending)
+    );
+}
+
+#[test]
+fn claims_wait_for_final_result() {
+    set_clock(PROPOSED_AT + 10);
+    let keys = WarKeys::unique();
+    let user = TestAccount::signer(0);
+    let (bet_key, bet_bump) = bet_address(&keys.war, &user.key);
+    let mut war = War {
+        status: all_statuses()[2],
+        num_bettors: 1,
+        ..keys.war_state(2)
+    };
+    war.contestants[0].total_bets = 1_000_000;
+    let bet = Bet {
+        user: user.key,
+        war: keys.war,
+        amounts: vec![1_000_000, 0],
+        fee_paid: 0,
+        penalty_paid: 0,
+        timestamp: 1_200,
+        is_claimed: false,
+        bump: bet_bump,
+    };
+    let mut accounts = [
+        TestAccount::program_owned(bet_key, &bet, BET_SPACE),
+        TestAccount::program_owned(keys.war, &war, WAR_SPACE),
+        keys.escrow_account(1_000_000),
+        keys.mint_account(),
+        TestAccount::token_account(Pubkey::new_unique(), keys.mint, user.key, 0),
+        user,
+        TestAccount::token_program(),
+    ];
+    assert_eq!(
+        process(instruction::ClaimWinnings {}, &mut accounts),
+        error_code(ErrorCode::WarNotSettled)
+    );
+}
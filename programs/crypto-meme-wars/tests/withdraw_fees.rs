@@ -0,0 +1,95 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{
+    error_code, events, fee_vault_account, platform_address, platform_state, process, TestAccount,
+    PLATFORM_SPACE,
+};
+use crypto_meme_wars::{
+    instruction, ErrorCode, FeesWithdrawn, PlatformConfig, PlatformRoles, PlatformState,
+};
+
+const FEE_MANAGER: Pubkey = Pubkey::new_from_array([1; 32]);
+const FEE_COLLECTOR: Pubkey = Pubkey::new_from_array([2; 32]);
+const VAULT_BALANCE: u64 = 90_000;
+
+// A fee vault holding VAULT_BALANCE, paid out by `caller` to a treasury owned by
+// `treasury_owner`
+fn withdraw_accounts(caller: Pubkey, treasury_owner: Pubkey) -> [TestAccount; 6] {
+    let platform = PlatformState {
+        roles: PlatformRoles {
+            fee_manager: FEE_MANAGER,
+            ..platform_state(FEE_MANAGER).roles
+        },
+        config: PlatformConfig {
+            fee_collector: FEE_COLLECTOR,
+            ..platform_state(FEE_MANAGER).config
+        },
+        ..platform_state(Pubkey::new_unique())
+    };
+    let mint = Pubkey::new_unique();
+    [
+        TestAccount::program_owned(platform_address().0, &platform, PLATFORM_SPACE),
+        fee_vault_account(mint, VAULT_BALANCE),
+        TestAccount::mint(mint, 6),
+        TestAccount::token_account(Pubkey::new_unique(), mint, treasury_owner, 0),
+        TestAccount::system(caller, 0).signed(),
+        TestAccount::token_program(),
+    ]
+}
+
+fn withdraw(accounts: &mut [TestAccount], amount: u64) -> std::result::Result<(), ProgramError> {
+    process(instruction::WithdrawFees { amount }, accounts)
+}
+
+#[test]
+fn fee_manager_pays_fees_to_the_fee_collector() {
+    let mut accounts = withdraw_accounts(FEE_MANAGER, FEE_COLLECTOR);
+    withdraw(&mut accounts, 60_000).unwrap();
+
+    assert_eq!(accounts[1].token_amount(), VAULT_BALANCE - 60_000);
+    assert_eq!(accounts[3].token_amount(), 60_000);
+    let [logged] = &events::<FeesWithdrawn>()[..] else {
+        panic!("expected one FeesWithdrawn event");
+    };
+    assert_eq!(logged.bet_mint, accounts[2].key);
+    assert_eq!(logged.treasury, accounts[3].key);
+    assert_eq!(logged.amount, 60_000);
+}
+
+#[test]
+fn fees_only_go_to_a_treasury_owned_by_the_fee_collector() {
+    // The fee manager can't redirect fees to an account of its own
+    let mut accounts = withdraw_accounts(FEE_MANAGER, FEE_MANAGER);
+    assert_eq!(
+        withdraw(&mut accounts, 60_000),
+        error_code(ErrorCode::UnauthorizedAction)
+    );
+    assert_eq!(accounts[1].token_amount(), VAULT_BALANCE);
+}
+
+#[test]
+fn only_the_fee_manager_withdraws_fees() {
+    for caller in [FEE_COLLECTOR, Pubkey::new_unique()] {
+        let mut accounts = withdraw_accounts(caller, FEE_COLLECTOR);
+        assert_eq!(
+            withdraw(&mut accounts, 60_000),
+            error_code(ErrorCode::UnauthorizedAction)
+        );
+    }
+}
+
+#[test]
+fn withdrawals_are_limited_to_the_vault_balance() {
+    let mut accounts = withdraw_accounts(FEE_MANAGER, FEE_COLLECTOR);
+    assert_eq!(
+        withdraw(&mut accounts, VAULT_BALANCE + 1),
+        error_code(ErrorCode::InsufficientFees)
+    );
+    assert_eq!(
+        withdraw(&mut accounts, 0),
+        error_code(ErrorCode::InvalidAmount)
+    );
+    withdraw(&mut accounts, VAULT_BALANCE).unwrap();
+    assert_eq!(accounts[3].token_amount(), VAULT_BALANCE);
+}
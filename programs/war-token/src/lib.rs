@@ -0,0 +1,1322 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+
+declare_id!("FzxiePrdDzqBF8uGoz6odC3gzqvPQFBsJKPTsN4B7XXJ");
+
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000; // fixed-point scale of acc_reward_per_share
+pub const SECONDS_PER_YEAR: u128 = 365 * 24 * 60 * 60;
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+pub const DEFAULT_UNSTAKE_COOLDOWN: i64 = 7 * SECONDS_PER_DAY;
+pub const MAX_UNSTAKE_COOLDOWN: i64 = 30 * SECONDS_PER_DAY;
+pub const BASE_BOOST_BPS: u16 = 10_000; // 1.0x: unlocked stake earns and votes at face value
+pub const MAX_STAKE_SNAPSHOTS: usize = 16; // stake history kept per account for vote snapshots
+
+#[program]
+pub mod war_token {
+    use super::*;
+
+    // Initialize the $WAR token mint
+    pub fn initialize_token(ctx: Context<InitializeToken>, total_supply: u64) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        token_data.authority = ctx.accounts.authority.key();
+        token_data.pending_authority = None;
+        token_data.config_manager = ctx.accounts.authority.key();
+        token_data.mint = ctx.accounts.mint.key();
+        token_data.proposal_count = 0;
+        token_data.total_supply = total_supply;
+        token_data.circulating_supply = 0;
+        token_data.staked_supply = 0;
+        token_data.burned_supply = 0;
+        token_data.fee_multiplier = 100; // 1.0x (in basis points)
+        token_data.staking_apr = 1500; // 15% APR (in basis points)
+        token_data.acc_reward_per_share = 0;
+        token_data.last_reward_time = Clock::get()?.unix_timestamp;
+        token_data.unstaking_supply = 0;
+        token_data.unstake_cooldown = DEFAULT_UNSTAKE_COOLDOWN;
+        token_data.governance_threshold = total_supply / 100; // 1% for proposals
+        token_data.bump = ctx.bumps.token_data;
+
+        // Mint initial supply to treasury
+        let cpi_accounts = token::MintTo {
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.treasury.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::mint_to(cpi_ctx, total_supply)?;
+
+        token_data.circulating_supply = total_supply;
+
+        emit!(TokenInitialized {
+            mint: ctx.accounts.mint.key(),
+            total_supply,
+            authority: ctx.accounts.authority.key(),
+        });
+
+        Ok(())
+    }
+
+    // Stake $WAR tokens for enhanced betting multipliers, optionally locking the whole
+    // position for a boost to rewards and voting weight
+    pub fn stake_tokens(ctx: Context<StakeTokens>, amount: u64, lockup: LockupTerm) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let token_data = &mut ctx.accounts.token_data;
+        let stake_account = &mut ctx.accounts.stake_account;
+        let now = Clock::get()?.unix_timestamp;
+
+        // Transfer tokens to staking pool
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.staking_pool.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        // Checkpoint rewards earned at the old stake before changing it
+        token_data.update_rewards(now)?;
+        if stake_account.owner == Pubkey::default() {
+            stake_account.owner = ctx.accounts.user.key();
+            stake_account.boost_bps = BASE_BOOST_BPS;
+            stake_account.bump = ctx.bumps.stake_account;
+        }
+        stake_account.checkpoint(token_data, now)?;
+
+        stake_account.staked_amount += amount;
+        stake_account.last_stake_time = now;
+        stake_account.extend_lockup(lockup, now);
+        stake_account.reset_reward_debt(token_data.acc_reward_per_share)?;
+        stake_account.record_snapshot(now);
+
+        // Update global staking data
+        token_data.staked_supply += amount;
+        token_data.circulating_supply -= amount;
+
+        check_solvency(&mut ctx.accounts.staking_pool, &ctx.accounts.token_data)?;
+
+        emit!(TokensStaked {
+            user: ctx.accounts.user.key(),
+            amount,
+            total_staked: stake_account.staked_amount,
+            lockup_end: stake_account.lockup_end,
+            boost_bps: stake_account.boost_bps,
+        });
+
+        Ok(())
+    }
+
+    // Start unstaking: the amount stops earning at once, drops out of votes on later
+    // proposals, and can be withdrawn once the cooldown has passed
+    pub fn request_unstake(ctx: Context<RequestUnstake>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let token_data = &mut ctx.accounts.token_data;
+        let stake_account = &mut ctx.accounts.stake_account;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(stake_account.staked_amount >= amount, ErrorCode::InsufficientStake);
+        require!(now >= stake_account.lockup_end, ErrorCode::StakeLocked);
+
+        token_data.update_rewards(now)?;
+        stake_account.checkpoint(token_data, now)?;
+
+        // Another request adds to the queued amount and restarts the cooldown
+        stake_account.staked_amount -= amount;
+        stake_account.unstaking_amount += amount;
+        stake_account.unstake_available_at = now + token_data.unstake_cooldown;
+        stake_account.last_stake_time = now;
+        stake_account.reset_reward_debt(token_data.acc_reward_per_share)?;
+        stake_account.record_snapshot(now);
+
+        // Queued principal stays in the staking pool until withdrawn
+        token_data.staked_supply -= amount;
+        token_data.unstaking_supply += amount;
+
+        emit!(UnstakeRequested {
+            user: ctx.accounts.user.key(),
+            amount,
+            unstaking_amount: stake_account.unstaking_amount,
+            available_at: stake_account.unstake_available_at,
+        });
+
+        Ok(())
+    }
+
+    // Withdraw everything queued by request_unstake once its cooldown has passed
+    pub fn withdraw_unstaked(ctx: Context<WithdrawUnstaked>) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let stake_account = &mut ctx.accounts.stake_account;
+        let now = Clock::get()?.unix_timestamp;
+
+        let amount = stake_account.unstaking_amount;
+        require!(amount > 0, ErrorCode::NothingToWithdraw);
+        require!(now >= stake_account.unstake_available_at, ErrorCode::CooldownActive);
+
+        token_data.update_rewards(now)?;
+        stake_account.checkpoint(token_data, now)?;
+
+        stake_account.unstaking_amount = 0;
+        token_data.unstaking_supply -= amount;
+        token_data.circulating_supply += amount;
+
+        // Return the principal from the staking pool
+        transfer_as_token_data(
+            &ctx.accounts.staking_pool,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_data,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+        check_solvency(&mut ctx.accounts.staking_pool, &ctx.accounts.token_data)?;
+
+        // Rewards come from the reward vault; anything it can't cover stays pending
+        let rewards = pay_rewards(
+            &mut ctx.accounts.stake_account,
+            &ctx.accounts.reward_vault,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_data,
+            &ctx.accounts.token_program,
+        )?;
+
+        emit!(TokensUnstaked {
+            user: ctx.accounts.user.key(),
+            amount,
+            rewards_claimed: rewards,
+        });
+
+        Ok(())
+    }
+
+    // Drop a lapsed lockup's boost from the position. Anyone may call this; rewards
+    // stop being boosted at lockup_end whether or not it is called.
+    pub fn expire_lockup(ctx: Context<ExpireLockup>) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let stake_account = &mut ctx.accounts.stake_account;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            stake_account.boost_bps != BASE_BOOST_BPS && now >= stake_account.lockup_end,
+            ErrorCode::LockupNotExpired
+        );
+
+        token_data.update_rewards(now)?;
+        stake_account.checkpoint(token_data, now)?;
+
+        emit!(LockupExpired {
+            user: stake_account.owner,
+            lockup_end: stake_account.lockup_end,
+        });
+
+        Ok(())
+    }
+
+    // Set how long request_unstake holds tokens before withdraw_unstaked releases them
+    pub fn set_unstake_cooldown(ctx: Context<UpdateStakingConfig>, cooldown: i64) -> Result<()> {
+        require!(
+            (0..=MAX_UNSTAKE_COOLDOWN).contains(&cooldown),
+            ErrorCode::InvalidCooldown
+        );
+
+        let token_data = &mut ctx.accounts.token_data;
+        let old_cooldown = token_data.unstake_cooldown;
+        token_data.unstake_cooldown = cooldown;
+
+        emit!(UnstakeCooldownUpdated {
+            old_cooldown,
+            new_cooldown: cooldown,
+        });
+
+        Ok(())
+    }
+
+    // Collect accrued rewards without touching principal, or restake them with `compound`
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, compound: bool) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let stake_account = &mut ctx.accounts.stake_account;
+
+        let now = Clock::get()?.unix_timestamp;
+        token_data.update_rewards(now)?;
+        stake_account.checkpoint(token_data, now)?;
+
+        let amount = if compound {
+            // Move the rewards from the vault into the staking pool as new stake
+            let amount = stake_account.pending_rewards.min(ctx.accounts.reward_vault.amount);
+            stake_account.pending_rewards -= amount;
+            stake_account.staked_amount += amount;
+            stake_account.reset_reward_debt(token_data.acc_reward_per_share)?;
+            stake_account.record_snapshot(now);
+            token_data.staked_supply += amount;
+            token_data.circulating_supply -= amount;
+
+            if amount > 0 {
+                transfer_as_token_data(
+                    &ctx.accounts.reward_vault,
+                    &ctx.accounts.staking_pool,
+                    &ctx.accounts.token_data,
+                    &ctx.accounts.token_program,
+                    amount,
+                )?;
+            }
+            check_solvency(&mut ctx.accounts.staking_pool, &ctx.accounts.token_data)?;
+            amount
+        } else {
+            pay_rewards(
+                &mut ctx.accounts.stake_account,
+                &ctx.accounts.reward_vault,
+                &ctx.accounts.user_token_account,
+                &ctx.accounts.token_data,
+                &ctx.accounts.token_program,
+            )?
+        };
+
+        emit!(RewardsClaimed {
+            user: ctx.accounts.user.key(),
+            amount,
+            compounded: compound,
+            total_staked: ctx.accounts.stake_account.staked_amount,
+        });
+
+        Ok(())
+    }
+
+    // Top up the reward vault that staking rewards are paid from; callable by anyone,
+    // e.g. the authority from the treasury or a platform fee buyback
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.funder_token_account.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        emit!(RewardsFunded {
+            funder: ctx.accounts.funder.key(),
+            amount,
+            vault_balance: ctx.accounts.reward_vault.amount + amount,
+        });
+
+        Ok(())
+    }
+
+    // Apply fee discounts for $WAR holders
+    pub fn calculate_fee_discount(ctx: Context<CalculateFeeDiscount>, base_fee: u64) -> Result<u64> {
+        let stake_account = &ctx.accounts.stake_account;
+
+        let user_balance = ctx.accounts.user_token_account.amount;
+        let staked_amount = stake_account.staked_amount;
+        let total_war_holdings = user_balance + staked_amount;
+
+        // Same tier table crypto_meme_wars::place_bet applies
+        let discount_bps = payout::war_discount_bps(total_war_holdings);
+        let discounted_fee = payout::discounted_fee(base_fee, discount_bps)?;
+
+        emit!(FeeDiscountCalculated {
+            user: ctx.accounts.user.key(),
+            base_fee,
+            discount_bps,
+            final_fee: discounted_fee,
+        });
+
+        Ok(discounted_fee)
+    }
+
+    // Burn tokens to reduce supply
+    pub fn burn_tokens(ctx: Context<BurnTokens>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let token_data = &mut ctx.accounts.token_data;
+
+        // Burn tokens
+        let cpi_accounts = token::Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::burn(cpi_ctx, amount)?;
+
+        // Update supply tracking
+        token_data.burned_supply += amount;
+        token_data.circulating_supply -= amount;
+
+        emit!(TokensBurned {
+            user: ctx.accounts.user.key(),
+            amount,
+            total_burned: token_data.burned_supply,
+        });
+
+        Ok(())
+    }
+
+    // Create governance proposal
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        title: String,
+        description: String,
+        voting_period: i64,
+    ) -> Result<()> {
+        let stake_account = &ctx.accounts.stake_account;
+        let token_data = &mut ctx.accounts.token_data;
+        let proposal = &mut ctx.accounts.proposal;
+
+        // Check if user has enough tokens to create proposal
+        let user_balance = ctx.accounts.user_token_account.amount;
+        let staked_amount = stake_account.staked_amount;
+        let total_holdings = user_balance + staked_amount;
+
+        require!(
+            total_holdings >= token_data.governance_threshold,
+            ErrorCode::InsufficientTokensForProposal
+        );
+
+        // Initialize proposal; its address is seeded with this number
+        proposal.number = token_data.proposal_count;
+        token_data.proposal_count = payout::add(token_data.proposal_count, 1)?;
+        proposal.proposer = ctx.accounts.user.key();
+        proposal.title = title;
+        proposal.description = description;
+        proposal.created_at = Clock::get()?.unix_timestamp;
+        proposal.voting_ends_at = Clock::get()?.unix_timestamp + voting_period;
+        proposal.votes_for = 0;
+        proposal.votes_against = 0;
+        proposal.executed = false;
+        proposal.bump = ctx.bumps.proposal;
+
+        emit!(ProposalCreated {
+            proposal_id: proposal.key(),
+            proposer: ctx.accounts.user.key(),
+            title: proposal.title.clone(),
+        });
+
+        Ok(())
+    }
+
+    // First step of a token authority handover; the new authority must accept
+    pub fn propose_authority(ctx: Context<TokenAdminOnly>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.token_data.pending_authority = Some(new_authority);
+
+        emit!(TokenAuthorityProposed {
+            authority: ctx.accounts.authority.key(),
+            pending_authority: new_authority,
+        });
+
+        Ok(())
+    }
+
+    // Hand the staking config to a separate key, so the admin key can stay cold
+    pub fn set_config_manager(ctx: Context<TokenAdminOnly>, config_manager: Pubkey) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let old_config_manager = std::mem::replace(&mut token_data.config_manager, config_manager);
+
+        emit!(ConfigManagerUpdated {
+            old_config_manager,
+            new_config_manager: config_manager,
+        });
+
+        Ok(())
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptTokenAuthority>) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let old_authority = token_data.authority;
+        token_data.authority = ctx.accounts.new_authority.key();
+        token_data.pending_authority = None;
+
+        emit!(TokenAuthorityTransferred {
+            old_authority,
+            new_authority: token_data.authority,
+        });
+
+        Ok(())
+    }
+
+    // Vote on governance proposal
+    pub fn vote_on_proposal(
+        ctx: Context<VoteOnProposal>,
+        vote_type: VoteType,
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let stake_account = &ctx.accounts.stake_account;
+        let vote_record = &mut ctx.accounts.vote_record;
+
+        require!(
+            Clock::get()?.unix_timestamp <= proposal.voting_ends_at,
+            ErrorCode::VotingPeriodEnded
+        );
+
+        require!(
+            vote_record.voter == Pubkey::default(),
+            ErrorCode::AlreadyVoted
+        );
+
+        // Only stake held before the proposal was created counts, so tokens moved to
+        // another wallet (or staked once the proposal is known) can't vote again
+        let voting_power = stake_account.voting_power_at(proposal.created_at)?;
+
+        require!(voting_power > 0, ErrorCode::NoVotingPower);
+
+        // Record vote
+        vote_record.voter = ctx.accounts.user.key();
+        vote_record.proposal = proposal.key();
+        vote_record.vote_type = vote_type.clone();
+        vote_record.voting_power = voting_power;
+        vote_record.bump = ctx.bumps.vote_record;
+
+        // Update proposal vote counts
+        match vote_type {
+            VoteType::For => proposal.votes_for += voting_power,
+            VoteType::Against => proposal.votes_against += voting_power,
+        }
+
+        emit!(VoteCast {
+            proposal_id: proposal.key(),
+            voter: ctx.accounts.user.key(),
+            vote_type,
+            voting_power,
+        });
+
+        Ok(())
+    }
+}
+
+// Helper functions
+// Move `amount` out of a token account owned by the token_data PDA
+fn transfer_as_token_data<'info>(
+    from: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_data: &Account<'info, TokenData>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[b"token_data".as_ref(), &[token_data.bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: from.to_account_info(),
+        to: to.to_account_info(),
+        authority: token_data.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+    token::transfer(cpi_ctx, amount)?;
+
+    Ok(())
+}
+
+// Pay settled rewards from the reward vault, capped by its balance; returns the amount paid
+fn pay_rewards<'info>(
+    stake_account: &mut Account<'info, StakeAccount>,
+    reward_vault: &Account<'info, TokenAccount>,
+    user_token_account: &Account<'info, TokenAccount>,
+    token_data: &Account<'info, TokenData>,
+    token_program: &Program<'info, Token>,
+) -> Result<u64> {
+    let paid = stake_account.pending_rewards.min(reward_vault.amount);
+    if paid > 0 {
+        transfer_as_token_data(reward_vault, user_token_account, token_data, token_program, paid)?;
+        stake_account.pending_rewards -= paid;
+    }
+    Ok(paid)
+}
+
+// Principal is never lent out: the staking pool must always cover every stake,
+// including amounts still cooling down after request_unstake
+fn check_solvency(staking_pool: &mut Account<TokenAccount>, token_data: &TokenData) -> Result<()> {
+    staking_pool.reload()?;
+    let owed = token_data.staked_supply as u128 + token_data.unstaking_supply as u128;
+    require!(
+        staking_pool.amount as u128 >= owed,
+        ErrorCode::StakingPoolInsolvent
+    );
+    Ok(())
+}
+
+// Account structures
+#[account]
+pub struct TokenData {
+    pub authority: Pubkey, // admin: assigns the config manager and hands over authority
+    pub pending_authority: Option<Pubkey>, // proposed authority awaiting accept_authority
+    pub config_manager: Pubkey, // set_unstake_cooldown
+    pub mint: Pubkey, // $WAR; every staking, burn and governance instruction is pinned to it
+    pub proposal_count: u64, // proposals created so far; seeds the next proposal's address
+    pub total_supply: u64,
+    pub circulating_supply: u64,
+    pub staked_supply: u64,
+    pub unstaking_supply: u64, // requested for unstake but still in the staking pool
+    pub burned_supply: u64,
+    pub fee_multiplier: u16,        // Basis points (100 = 1%)
+    pub staking_apr: u16,          // Basis points (1500 = 15%)
+    pub governance_threshold: u64,  // Minimum tokens needed for proposals
+    pub acc_reward_per_share: u128, // rewards per staked token since launch, scaled by REWARD_PRECISION
+    pub last_reward_time: i64,      // when acc_reward_per_share was last brought up to date
+    pub unstake_cooldown: i64,      // seconds between request_unstake and withdraw_unstaked
+    pub bump: u8,
+}
+
+impl TokenData {
+    // Accrue staking_apr emissions for the time since last_reward_time. Every staked
+    // token earns the same amount, so accrual is exact for any number of stakers.
+    pub fn update_rewards(&mut self, now: i64) -> Result<()> {
+        if now <= self.last_reward_time {
+            return Ok(());
+        }
+        let accrued = self.accrual(now - self.last_reward_time)?;
+        self.acc_reward_per_share = self
+            .acc_reward_per_share
+            .checked_add(accrued)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.last_reward_time = now;
+        Ok(())
+    }
+
+    // acc_reward_per_share as it stood at an earlier `time`. staking_apr never changes,
+    // so the accumulator grows linearly and can be wound back.
+    pub fn acc_reward_per_share_at(&self, time: i64) -> Result<u128> {
+        let accrued = self.accrual((self.last_reward_time - time).max(0))?;
+        Ok(self.acc_reward_per_share.saturating_sub(accrued))
+    }
+
+    fn accrual(&self, elapsed: i64) -> Result<u128> {
+        Ok(REWARD_PRECISION
+            .checked_mul(self.staking_apr as u128 * elapsed as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            / (10_000 * SECONDS_PER_YEAR))
+    }
+}
+
+#[account]
+pub struct StakeAccount {
+    pub owner: Pubkey,
+    pub staked_amount: u64,
+    pub last_stake_time: i64,
+    pub pending_rewards: u64, // settled but not yet paid out
+    pub reward_debt: u128, // boosted_amount() * acc_reward_per_share at the last checkpoint
+    pub lockup_end: i64, // request_unstake is refused before this
+    pub boost_bps: u16, // reward and voting weight of the lockup, BASE_BOOST_BPS when unlocked
+    pub unstaking_amount: u64, // queued by request_unstake; no longer earns
+    pub unstake_available_at: i64, // when withdraw_unstaked may release unstaking_amount
+    pub snapshots: Vec<StakeSnapshot>, // position after each change, oldest first
+    pub pruned_until: Option<i64>, // voting power up to this time went with dropped snapshots
+    pub bump: u8,
+}
+
+// StakeAccount's staked position from `timestamp` until the next snapshot
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct StakeSnapshot {
+    pub timestamp: i64,
+    pub staked_amount: u64,
+    pub boost_bps: u16,
+    pub lockup_end: i64,
+}
+
+impl StakeSnapshot {
+    pub const SPACE: usize = 8 + 8 + 2 + 8;
+}
+
+impl StakeAccount {
+    // Stake weighted by the lockup boost
+    pub fn boosted_amount(&self) -> Result<u64> {
+        payout::mul_div_floor(self.staked_amount, self.boost_bps as u64, BASE_BOOST_BPS as u64)
+    }
+
+    fn accrued(&self, acc_reward_per_share: u128) -> Result<u128> {
+        let scaled = (self.boosted_amount()? as u128)
+            .checked_mul(acc_reward_per_share)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(scaled / REWARD_PRECISION)
+    }
+
+    // Move rewards earned since the last checkpoint into pending_rewards
+    pub fn settle_rewards(&mut self, acc_reward_per_share: u128) -> Result<()> {
+        let earned = self
+            .accrued(acc_reward_per_share)?
+            .checked_sub(self.reward_debt)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.pending_rewards = u64::try_from(earned)
+            .ok()
+            .and_then(|earned| self.pending_rewards.checked_add(earned))
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.reward_debt = self.accrued(acc_reward_per_share)?;
+        Ok(())
+    }
+
+    // Checkpoint after staked_amount or boost_bps changes, so only future accrual counts
+    pub fn reset_reward_debt(&mut self, acc_reward_per_share: u128) -> Result<()> {
+        self.reward_debt = self.accrued(acc_reward_per_share)?;
+        Ok(())
+    }
+
+    // Settle rewards up to `now` from an up-to-date `token_data`. A lapsed lockup's boost
+    // only counts until lockup_end; the time since then accrues at face value.
+    pub fn checkpoint(&mut self, token_data: &TokenData, now: i64) -> Result<()> {
+        if now >= self.lockup_end && self.boost_bps != BASE_BOOST_BPS {
+            let acc_at_lockup_end = token_data.acc_reward_per_share_at(self.lockup_end)?;
+            // Rounding can put the wound-back value a hair below the last checkpoint's
+            if self.accrued(acc_at_lockup_end)? > self.reward_debt {
+                self.settle_rewards(acc_at_lockup_end)?;
+            }
+            self.boost_bps = BASE_BOOST_BPS;
+            self.reset_reward_debt(acc_at_lockup_end)?;
+        }
+        self.settle_rewards(token_data.acc_reward_per_share)
+    }
+
+    // Record the position after staked_amount or the lockup changes. Changes within one
+    // second collapse into one snapshot; past MAX_STAKE_SNAPSHOTS the oldest is dropped,
+    // leaving the position unknown up to the next one.
+    pub fn record_snapshot(&mut self, now: i64) {
+        if self.snapshots.last().is_some_and(|last| last.timestamp == now) {
+            self.snapshots.pop();
+        } else if self.snapshots.len() == MAX_STAKE_SNAPSHOTS {
+            self.snapshots.remove(0);
+            self.pruned_until = Some(self.snapshots[0].timestamp);
+        }
+        self.snapshots.push(StakeSnapshot {
+            timestamp: now,
+            staked_amount: self.staked_amount,
+            boost_bps: self.boost_bps,
+            lockup_end: self.lockup_end,
+        });
+    }
+
+    // Boosted stake held strictly before `time`, with the boost only while its lockup ran.
+    // Fails if the position changed too often since then for its snapshot to be retained.
+    pub fn voting_power_at(&self, time: i64) -> Result<u64> {
+        require!(
+            self.pruned_until.is_none_or(|pruned_until| time > pruned_until),
+            ErrorCode::StakeHistoryPruned
+        );
+        let Some(snapshot) = self.snapshots.iter().rev().find(|s| s.timestamp < time) else {
+            return Ok(0);
+        };
+        let boost_bps = if time < snapshot.lockup_end { snapshot.boost_bps } else { BASE_BOOST_BPS };
+        payout::mul_div_floor(snapshot.staked_amount, boost_bps as u64, BASE_BOOST_BPS as u64)
+    }
+
+    // A lockup covers the whole position. A term that ends later replaces the current
+    // one along with its boost; one that ends sooner leaves it as is.
+    pub fn extend_lockup(&mut self, term: LockupTerm, now: i64) {
+        if term == LockupTerm::None {
+            return;
+        }
+        let lockup_end = now + term.duration();
+        if lockup_end > self.lockup_end {
+            self.lockup_end = lockup_end;
+            self.boost_bps = term.boost_bps();
+        }
+    }
+}
+
+#[account]
+pub struct Proposal {
+    pub number: u64, // position in TokenData::proposal_count order
+    pub proposer: Pubkey,
+    pub title: String,
+    pub description: String,
+    pub created_at: i64,
+    pub voting_ends_at: i64,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+#[account]
+pub struct VoteRecord {
+    pub voter: Pubkey,
+    pub proposal: Pubkey,
+    pub vote_type: VoteType,
+    pub voting_power: u64,
+    pub bump: u8,
+}
+
+// Context structures
+#[derive(Accounts)]
+pub struct InitializeToken<'info> {
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = 6,
+        mint::authority = authority,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"token_data"],
+        bump,
+        space = 8 + 32 + 1 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 2 + 2 + 8 + 16 + 8 + 8 + 1
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = authority,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    // Staking rewards are paid from here, never from stakers' principal
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"reward_vault"],
+        bump,
+        token::mint = mint,
+        token::authority = token_data,
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct StakeTokens<'info> {
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"stake", user.key().as_ref()],
+        bump,
+        space = 8 + 32 + 8 + 8 + 8 + 16 + 8 + 2 + 8 + 8
+            + 4 + MAX_STAKE_SNAPSHOTS * StakeSnapshot::SPACE + 1 + 8 + 1
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"token_data"],
+        bump = token_data.bump
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = token_data,
+    )]
+    pub staking_pool: Account<'info, TokenAccount>,
+
+    #[account(address = token_data.mint @ ErrorCode::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", user.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"token_data"],
+        bump = token_data.bump
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawUnstaked<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", user.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"token_data"],
+        bump = token_data.bump
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = token_data,
+    )]
+    pub staking_pool: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_vault"],
+        bump,
+        token::mint = mint,
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    #[account(address = token_data.mint @ ErrorCode::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireLockup<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", stake_account.owner.as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"token_data"],
+        bump = token_data.bump
+    )]
+    pub token_data: Account<'info, TokenData>,
+}
+
+#[derive(Accounts)]
+pub struct CalculateFeeDiscount<'info> {
+    #[account(
+        seeds = [b"stake", user.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        seeds = [b"token_data"],
+        bump = token_data.bump
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(address = token_data.mint @ ErrorCode::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BurnTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_data"],
+        bump = token_data.bump
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(mut, address = token_data.mint @ ErrorCode::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"proposal", token_data.proposal_count.to_le_bytes().as_ref()],
+        bump,
+        space = 8 + 8 + 32 + 4 + 64 + 4 + 256 + 8 + 8 + 8 + 8 + 1 + 1
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        seeds = [b"stake", user.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"token_data"],
+        bump = token_data.bump
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(address = token_data.mint @ ErrorCode::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VoteOnProposal<'info> {
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"vote", proposal.key().as_ref(), user.key().as_ref()],
+        bump,
+        space = 8 + 32 + 32 + 1 + 8 + 1
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        seeds = [b"stake", user.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Admin-only: authority handover and role assignment
+#[derive(Accounts)]
+pub struct TokenAdminOnly<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_data"],
+        bump = token_data.bump,
+        constraint = token_data.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateStakingConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_data"],
+        bump = token_data.bump,
+        constraint = token_data.config_manager == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptTokenAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_data"],
+        bump = token_data.bump,
+        constraint = token_data.pending_authority == Some(new_authority.key()) @ ErrorCode::Unauthorized
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", user.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"token_data"],
+        bump = token_data.bump
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = token_data,
+    )]
+    pub staking_pool: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_vault"],
+        bump,
+        token::mint = mint,
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    #[account(address = token_data.mint @ ErrorCode::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    #[account(
+        mut,
+        seeds = [b"reward_vault"],
+        bump,
+        token::mint = mint,
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = funder,
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"token_data"],
+        bump = token_data.bump
+    )]
+    pub token_data: Account<'info, TokenData>,
+
+    #[account(address = token_data.mint @ ErrorCode::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+    pub funder: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+// Enums and events
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum VoteType {
+    For,
+    Against,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum LockupTerm {
+    None,
+    Days30,
+    Days90,
+    Days180,
+}
+
+impl LockupTerm {
+    pub fn duration(&self) -> i64 {
+        match self {
+            LockupTerm::None => 0,
+            LockupTerm::Days30 => 30 * SECONDS_PER_DAY,
+            LockupTerm::Days90 => 90 * SECONDS_PER_DAY,
+            LockupTerm::Days180 => 180 * SECONDS_PER_DAY,
+        }
+    }
+
+    // Reward and voting weight while locked, in basis points
+    pub fn boost_bps(&self) -> u16 {
+        match self {
+            LockupTerm::None => BASE_BOOST_BPS,
+            LockupTerm::Days30 => 11_000,  // 1.1x
+            LockupTerm::Days90 => 12_500,  // 1.25x
+            LockupTerm::Days180 => 15_000, // 1.5x
+        }
+    }
+}
+
+#[event]
+pub struct TokenInitialized {
+    pub mint: Pubkey,
+    pub total_supply: u64,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct TokensStaked {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+    pub lockup_end: i64,
+    pub boost_bps: u16,
+}
+
+#[event]
+pub struct UnstakeRequested {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub unstaking_amount: u64,
+    pub available_at: i64,
+}
+
+#[event]
+pub struct TokensUnstaked {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub rewards_claimed: u64,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub compounded: bool, // restaked rather than paid out
+    pub total_staked: u64,
+}
+
+#[event]
+pub struct RewardsFunded {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub vault_balance: u64,
+}
+
+#[event]
+pub struct LockupExpired {
+    pub user: Pubkey,
+    pub lockup_end: i64,
+}
+
+#[event]
+pub struct UnstakeCooldownUpdated {
+    pub old_cooldown: i64,
+    pub new_cooldown: i64,
+}
+
+#[event]
+pub struct FeeDiscountCalculated {
+    pub user: Pubkey,
+    pub base_fee: u64,
+    pub discount_bps: u16,
+    pub final_fee: u64,
+}
+
+#[event]
+pub struct TokensBurned {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub total_burned: u64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal_id: Pubkey,
+    pub proposer: Pubkey,
+    pub title: String,
+}
+
+#[event]
+pub struct TokenAuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct ConfigManagerUpdated {
+    pub old_config_manager: Pubkey,
+    pub new_config_manager: Pubkey,
+}
+
+#[event]
+pub struct TokenAuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal_id: Pubkey,
+    pub voter: Pubkey,
+    pub vote_type: VoteType,
+    pub voting_power: u64,
+}
+
+// Error codes
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Invalid amount provided")]
+    InvalidAmount,
+    #[msg("Insufficient staked tokens")]
+    InsufficientStake,
+    #[msg("Insufficient tokens for creating proposal")]
+    InsufficientTokensForProposal,
+    #[msg("Voting period has ended")]
+    VotingPeriodEnded,
+    #[msg("User has already voted on this proposal")]
+    AlreadyVoted,
+    #[msg("User has no voting power")]
+    NoVotingPower,
+    #[msg("Unauthorized action")]
+    Unauthorized,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Staking pool holds less than the staked supply")]
+    StakingPoolInsolvent,
+    #[msg("Stake is locked until its lockup ends")]
+    StakeLocked,
+    #[msg("Unstake cooldown has not passed")]
+    CooldownActive,
+    #[msg("No unstaked tokens to withdraw")]
+    NothingToWithdraw,
+    #[msg("Lockup has not expired")]
+    LockupNotExpired,
+    #[msg("Unstake cooldown out of range")]
+    InvalidCooldown,
+    #[msg("Mint is not the $WAR mint")]
+    InvalidMint,
+    #[msg("Stake history from that time is no longer kept")]
+    StakeHistoryPruned,
+}
\ No newline at end of file
@@ -0,0 +1,212 @@
+//! `war_token` accounts and addresses on top of the shared `test_harness`,
+//! which drives the program with locally crafted accounts.
+
+#![allow(dead_code, unused_imports)]
+
+use std::collections::HashMap;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::{system_program, InstructionData};
+use anchor_spl::token::spl_token;
+use war_token::{
+    instruction, LockupTerm, StakeAccount, StakeSnapshot, TokenData, DEFAULT_UNSTAKE_COOLDOWN,
+    MAX_STAKE_SNAPSHOTS,
+};
+
+pub use test_harness::{ata_address, error_code, return_data, set_clock, TestAccount};
+
+pub const TOKEN_DATA_SPACE: usize =
+    8 + 32 + 1 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 2 + 2 + 8 + 16 + 8 + 8 + 1;
+pub const STAKE_SPACE: usize = 8 + 32 + 8 + 8 + 8 + 16 + 8 + 2 + 8 + 8
+    + 4 + MAX_STAKE_SNAPSHOTS * StakeSnapshot::SPACE + 1 + 8 + 1;
+
+pub fn token_data_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"token_data"], &war_token::ID)
+}
+
+pub fn reward_vault_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"reward_vault"], &war_token::ID)
+}
+
+pub fn stake_address(user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"stake", user.as_ref()], &war_token::ID)
+}
+
+/// TokenData as `initialize_token` leaves it, with every role held by `authority`.
+pub fn token_data(authority: Pubkey, mint: Pubkey) -> TokenData {
+    TokenData {
+        authority,
+        pending_authority: None,
+        config_manager: authority,
+        mint,
+        proposal_count: 0,
+        total_supply: 1_000_000_000_000,
+        circulating_supply: 1_000_000_000_000,
+        staked_supply: 0,
+        unstaking_supply: 0,
+        burned_supply: 0,
+        fee_multiplier: 100,
+        staking_apr: 1500,
+        governance_threshold: 10_000_000_000,
+        acc_reward_per_share: 0,
+        last_reward_time: 0,
+        unstake_cooldown: DEFAULT_UNSTAKE_COOLDOWN,
+        bump: token_data_address().1,
+    }
+}
+
+/// A staker's wallet, $WAR token account and stake account.
+pub struct Staker {
+    pub wallet: Pubkey,
+    pub tokens: Pubkey,
+    pub stake: Pubkey,
+}
+
+/// The staking program's accounts plus its stakers', kept by address so each
+/// instruction runs against the state the previous one left behind.
+pub struct Pool {
+    pub mint: Pubkey,
+    pub token_data: Pubkey,
+    pub staking_pool: Pubkey,
+    pub reward_vault: Pubkey,
+    accounts: HashMap<Pubkey, TestAccount>,
+}
+
+impl Pool {
+    pub fn new(authority: Pubkey, reward_vault_balance: u64) -> Self {
+        let mint = Pubkey::new_unique();
+        let token_data = token_data_address().0;
+        let staking_pool = ata_address(&token_data, &mint);
+        let reward_vault = reward_vault_address().0;
+        let mut pool = Self {
+            mint,
+            token_data,
+            staking_pool,
+            reward_vault,
+            accounts: HashMap::new(),
+        };
+        for account in [
+            TestAccount::program_owned(
+                token_data,
+                &self::token_data(authority, mint),
+                TOKEN_DATA_SPACE,
+            ),
+            TestAccount::token_account(staking_pool, mint, token_data, 0),
+            TestAccount::token_account(reward_vault, mint, token_data, reward_vault_balance),
+            TestAccount::mint(mint, 6),
+            TestAccount::token_program(),
+            TestAccount::system_program(),
+        ] {
+            pool.insert(account);
+        }
+        pool
+    }
+
+    pub fn insert(&mut self, account: TestAccount) {
+        self.accounts.insert(account.key, account);
+    }
+
+    /// A new wallet holding `balance` $WAR and no stake yet.
+    pub fn staker(&mut self, balance: u64) -> Staker {
+        let wallet = TestAccount::signer(1_000_000_000);
+        let staker = Staker {
+            wallet: wallet.key,
+            tokens: ata_address(&wallet.key, &self.mint),
+            stake: stake_address(&wallet.key).0,
+        };
+        self.insert(wallet);
+        self.insert(TestAccount::token_account(staker.tokens, self.mint, staker.wallet, balance));
+        self.insert(TestAccount::uninitialized(staker.stake, STAKE_SPACE));
+        staker
+    }
+
+    /// Runs `ix` over the accounts at `keys` and stores what it leaves behind.
+    pub fn run<I: InstructionData>(&mut self, ix: I, keys: &[Pubkey]) -> ProgramResult {
+        let mut accounts: Vec<TestAccount> = keys.iter().map(|key| self.accounts[key].clone()).collect();
+        process(ix, &mut accounts)?;
+        for account in accounts {
+            self.insert(account);
+        }
+        Ok(())
+    }
+
+    pub fn stake(&mut self, staker: &Staker, amount: u64, lockup: LockupTerm) -> ProgramResult {
+        let keys = [
+            staker.stake,
+            self.token_data,
+            staker.tokens,
+            self.staking_pool,
+            self.mint,
+            staker.wallet,
+            spl_token::ID,
+            system_program::ID,
+        ];
+        self.run(instruction::StakeTokens { amount, lockup }, &keys)
+    }
+
+    pub fn request_unstake(&mut self, staker: &Staker, amount: u64) -> ProgramResult {
+        let keys = [staker.stake, self.token_data, staker.wallet];
+        self.run(instruction::RequestUnstake { amount }, &keys)
+    }
+
+    pub fn withdraw(&mut self, staker: &Staker) -> ProgramResult {
+        let keys = self.payout_keys(staker);
+        self.run(instruction::WithdrawUnstaked {}, &keys)
+    }
+
+    pub fn claim(&mut self, staker: &Staker, compound: bool) -> ProgramResult {
+        let keys = self.payout_keys(staker);
+        self.run(instruction::ClaimRewards { compound }, &keys)
+    }
+
+    pub fn expire_lockup(&mut self, staker: &Staker) -> ProgramResult {
+        let keys = [staker.stake, self.token_data];
+        self.run(instruction::ExpireLockup {}, &keys)
+    }
+
+    // WithdrawUnstaked and ClaimRewards take the same accounts
+    fn payout_keys(&self, staker: &Staker) -> [Pubkey; 8] {
+        [
+            staker.stake,
+            self.token_data,
+            staker.tokens,
+            self.staking_pool,
+            self.reward_vault,
+            self.mint,
+            staker.wallet,
+            spl_token::ID,
+        ]
+    }
+
+    pub fn balance(&self, key: &Pubkey) -> u64 {
+        self.accounts[key].token_amount()
+    }
+
+    pub fn state<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        self.accounts[key].state()
+    }
+
+    pub fn token_data(&self) -> TokenData {
+        self.accounts[&self.token_data].state()
+    }
+
+    pub fn stake_account(&self, staker: &Staker) -> StakeAccount {
+        self.accounts[&staker.stake].state()
+    }
+
+    /// The staking pool holds exactly the principal owed to stakers, nothing lent out
+    /// and no rewards mixed in.
+    pub fn assert_solvent(&self) {
+        let token_data = self.token_data();
+        assert_eq!(
+            self.balance(&self.staking_pool),
+            token_data.staked_supply + token_data.unstaking_supply
+        );
+    }
+}
+
+/// Runs one instruction through the `war_token` entrypoint.
+pub fn process<I: InstructionData>(ix: I, accounts: &mut [TestAccount]) -> ProgramResult {
+    test_harness::process(war_token::entry, &war_token::ID, ix, accounts)
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{
+    error_code, process, set_clock, token_data, token_data_address, TestAccount, TOKEN_DATA_SPACE,
+};
+use war_token::{instruction, ErrorCode, TokenData, MAX_UNSTAKE_COOLDOWN};
+
+fn token_data_account(state: &TokenData) -> TestAccount {
+    TestAccount::program_owned(token_data_address().0, state, TOKEN_DATA_SPACE)
+}
+
+#[test]
+fn only_the_config_manager_sets_the_cooldown() {
+    set_clock(1_000);
+    let admin = Pubkey::new_unique();
+    let config_manager = Pubkey::new_unique();
+    let mut accounts = [
+        token_data_account(&token_data(admin, Pubkey::new_unique())),
+        TestAccount::system(admin, 0).signed(),
+    ];
+
+    process(instruction::SetConfigManager { config_manager }, &mut accounts).unwrap();
+    assert_eq!(accounts[0].state::<TokenData>().config_manager, config_manager);
+
+    // The admin no longer tunes staking once it has handed the role off
+    assert_eq!(
+        process(instruction::SetUnstakeCooldown { cooldown: 0 }, &mut accounts),
+        error_code(ErrorCode::Unauthorized)
+    );
+
+    accounts[1] = TestAccount::system(config_manager, 0).signed();
+    process(
+        instruction::SetUnstakeCooldown {
+            cooldown: MAX_UNSTAKE_COOLDOWN,
+        },
+        &mut accounts,
+    )
+    .unwrap();
+    assert_eq!(
+        accounts[0].state::<TokenData>().unstake_cooldown,
+        MAX_UNSTAKE_COOLDOWN
+    );
+}
+
+#[test]
+fn only_the_admin_assigns_the_config_manager() {
+    set_clock(1_000);
+    let admin = Pubkey::new_unique();
+    let config_manager = Pubkey::new_unique();
+    let state = TokenData {
+        config_manager,
+        ..token_data(admin, Pubkey::new_unique())
+    };
+    let mut accounts = [
+        token_data_account(&state),
+        TestAccount::system(config_manager, 0).signed(),
+    ];
+
+    assert_eq!(
+        process(
+            instruction::SetConfigManager {
+                config_manager: Pubkey::new_unique(),
+            },
+            &mut accounts
+        ),
+        error_code(ErrorCode::Unauthorized)
+    );
+    assert_eq!(
+        process(
+            instruction::ProposeAuthority {
+                new_authority: config_manager,
+            },
+            &mut accounts
+        ),
+        error_code(ErrorCode::Unauthorized)
+    );
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{set_clock, Pool};
+use war_token::{LockupTerm, BASE_BOOST_BPS, SECONDS_PER_DAY};
+
+// 15% APR over a third of a year accrues exactly 5% per staked token
+const THIRD_OF_YEAR: i64 = 365 * 24 * 60 * 60 / 3;
+
+#[test]
+fn overlapping_stakers_earn_the_same_per_token() {
+    let mut pool = Pool::new(Pubkey::new_unique(), 1_000_000_000);
+    let alice = pool.staker(1_000_000);
+    let bob = pool.staker(3_000_000);
+
+    // Alice enters first and leaves first; Bob enters later, stakes three times as
+    // much, and leaves last. Both hold for the same three thirds of a year.
+    set_clock(0);
+    pool.stake(&alice, 1_000_000, LockupTerm::None).unwrap();
+
+    set_clock(THIRD_OF_YEAR);
+    pool.stake(&bob, 3_000_000, LockupTerm::None).unwrap();
+
+    // Claiming part-way checkpoints Alice without changing her total
+    set_clock(2 * THIRD_OF_YEAR);
+    pool.claim(&alice, false).unwrap();
+
+    set_clock(3 * THIRD_OF_YEAR);
+    pool.request_unstake(&alice, 1_000_000).unwrap();
+
+    set_clock(4 * THIRD_OF_YEAR);
+    pool.request_unstake(&bob, 3_000_000).unwrap();
+
+    // Nothing accrues while unstaking, however late the rewards are claimed
+    set_clock(6 * THIRD_OF_YEAR);
+    pool.claim(&bob, false).unwrap();
+    pool.claim(&alice, false).unwrap();
+
+    let alice_rewards = pool.balance(&alice.tokens);
+    let bob_rewards = pool.balance(&bob.tokens);
+    assert_eq!(alice_rewards, 150_000);
+    assert_eq!(bob_rewards, 3 * alice_rewards);
+    assert_eq!(pool.balance(&pool.reward_vault), 1_000_000_000 - 600_000);
+}
+
+#[test]
+fn stakers_entering_in_either_order_are_paid_alike() {
+    let payouts = |alice_first: bool| {
+        let mut pool = Pool::new(Pubkey::new_unique(), 1_000_000_000);
+        let alice = pool.staker(2_000_000);
+        let bob = pool.staker(2_000_000);
+        let (first, second) = if alice_first { (&alice, &bob) } else { (&bob, &alice) };
+
+        set_clock(0);
+        pool.stake(first, 2_000_000, LockupTerm::None).unwrap();
+        set_clock(THIRD_OF_YEAR);
+        pool.stake(second, 2_000_000, LockupTerm::None).unwrap();
+        set_clock(2 * THIRD_OF_YEAR);
+        pool.request_unstake(second, 2_000_000).unwrap();
+        pool.claim(second, false).unwrap();
+        set_clock(3 * THIRD_OF_YEAR);
+        pool.request_unstake(first, 2_000_000).unwrap();
+        pool.claim(first, false).unwrap();
+
+        (pool.balance(&alice.tokens), pool.balance(&bob.tokens))
+    };
+
+    // Whoever stakes first holds for three thirds, the other for one
+    assert_eq!(payouts(true), (300_000, 100_000));
+    assert_eq!(payouts(false), (100_000, 300_000));
+}
+
+#[test]
+fn lapsed_lockup_earns_at_face_value_whether_or_not_it_is_expired() {
+    let mut pool = Pool::new(Pubkey::new_unique(), 1_000_000_000);
+    let alice = pool.staker(1_000_000);
+    let bob = pool.staker(1_000_000);
+
+    set_clock(0);
+    pool.stake(&alice, 1_000_000, LockupTerm::Days180).unwrap();
+    pool.stake(&bob, 1_000_000, LockupTerm::Days180).unwrap();
+
+    // Alice's lockup is expired the moment it ends; nobody touches Bob's
+    let lockup_end = 180 * SECONDS_PER_DAY;
+    set_clock(lockup_end);
+    pool.expire_lockup(&alice).unwrap();
+
+    set_clock(lockup_end + THIRD_OF_YEAR);
+    pool.claim(&alice, false).unwrap();
+    pool.claim(&bob, false).unwrap();
+
+    // 1.5x for 180 days, then 5% at face value for the third of a year after
+    assert_eq!(pool.balance(&alice.tokens), 110_958 + 50_000);
+    assert_eq!(pool.balance(&bob.tokens), pool.balance(&alice.tokens));
+    assert_eq!(pool.stake_account(&bob).boost_bps, BASE_BOOST_BPS);
+}
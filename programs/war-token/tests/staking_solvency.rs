@@ -0,0 +1,85 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{ata_address, error_code, set_clock, Pool, TestAccount};
+use war_token::{instruction, ErrorCode, LockupTerm, DEFAULT_UNSTAKE_COOLDOWN};
+
+const YEAR: i64 = 365 * 24 * 60 * 60;
+
+#[test]
+fn staking_pool_covers_principal_after_every_step() {
+    let mut pool = Pool::new(Pubkey::new_unique(), 1_000_000_000);
+    let alice = pool.staker(10_000_000);
+    let bob = pool.staker(10_000_000);
+
+    set_clock(0);
+    pool.stake(&alice, 4_000_000, LockupTerm::None).unwrap();
+    pool.assert_solvent();
+    pool.stake(&bob, 6_000_000, LockupTerm::Days30).unwrap();
+    pool.assert_solvent();
+
+    // Rewards come out of the reward vault, never the staking pool
+    set_clock(YEAR);
+    pool.claim(&alice, false).unwrap();
+    pool.assert_solvent();
+    pool.claim(&bob, true).unwrap();
+    pool.assert_solvent();
+    // Bob's 1.1x boost only lasted his 30-day lockup
+    assert_eq!(pool.token_data().staked_supply, 10_000_000 + 907_397);
+
+    pool.request_unstake(&alice, 1_000_000).unwrap();
+    pool.assert_solvent();
+    pool.request_unstake(&bob, 6_907_397).unwrap();
+    pool.assert_solvent();
+    assert_eq!(pool.token_data().unstaking_supply, 7_907_397);
+
+    set_clock(YEAR + DEFAULT_UNSTAKE_COOLDOWN);
+    pool.withdraw(&alice).unwrap();
+    pool.assert_solvent();
+    pool.withdraw(&bob).unwrap();
+    pool.assert_solvent();
+
+    pool.claim(&alice, true).unwrap();
+    pool.assert_solvent();
+
+    let token_data = pool.token_data();
+    assert_eq!(token_data.unstaking_supply, 0);
+    assert_eq!(pool.balance(&pool.staking_pool), token_data.staked_supply);
+    assert_eq!(pool.stake_account(&alice).staked_amount, token_data.staked_supply);
+}
+
+#[test]
+fn staking_rejects_another_mint() {
+    set_clock(0);
+    let mut pool = Pool::new(Pubkey::new_unique(), 0);
+    let staker = pool.staker(0);
+
+    // A worthless mint with its own ATAs satisfies every other constraint
+    let fake_mint = Pubkey::new_unique();
+    let fake_tokens = ata_address(&staker.wallet, &fake_mint);
+    let fake_pool = ata_address(&pool.token_data, &fake_mint);
+    pool.insert(TestAccount::mint(fake_mint, 6));
+    pool.insert(TestAccount::token_account(fake_tokens, fake_mint, staker.wallet, 1_000_000));
+    pool.insert(TestAccount::token_account(fake_pool, fake_mint, pool.token_data, 0));
+
+    let keys = [
+        staker.stake,
+        pool.token_data,
+        fake_tokens,
+        fake_pool,
+        fake_mint,
+        staker.wallet,
+        anchor_spl::token::ID,
+        anchor_lang::system_program::ID,
+    ];
+    assert_eq!(
+        pool.run(
+            instruction::StakeTokens {
+                amount: 1_000_000,
+                lockup: LockupTerm::None,
+            },
+            &keys
+        ),
+        error_code(ErrorCode::InvalidMint)
+    );
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{error_code, set_clock, Pool};
+use war_token::{ErrorCode, LockupTerm, DEFAULT_UNSTAKE_COOLDOWN, SECONDS_PER_DAY};
+
+#[test]
+fn withdraw_waits_for_the_cooldown() {
+    let mut pool = Pool::new(Pubkey::new_unique(), 0);
+    let staker = pool.staker(1_000_000);
+
+    set_clock(0);
+    pool.stake(&staker, 1_000_000, LockupTerm::None).unwrap();
+    assert_eq!(pool.withdraw(&staker), error_code(ErrorCode::NothingToWithdraw));
+
+    set_clock(100);
+    pool.request_unstake(&staker, 400_000).unwrap();
+    assert_eq!(
+        pool.stake_account(&staker).unstake_available_at,
+        100 + DEFAULT_UNSTAKE_COOLDOWN
+    );
+
+    set_clock(100 + DEFAULT_UNSTAKE_COOLDOWN - 1);
+    assert_eq!(pool.withdraw(&staker), error_code(ErrorCode::CooldownActive));
+
+    // A second request adds to the queue and restarts the cooldown for all of it
+    pool.request_unstake(&staker, 100_000).unwrap();
+    set_clock(100 + DEFAULT_UNSTAKE_COOLDOWN);
+    assert_eq!(pool.withdraw(&staker), error_code(ErrorCode::CooldownActive));
+
+    set_clock(100 + 2 * DEFAULT_UNSTAKE_COOLDOWN - 1);
+    pool.withdraw(&staker).unwrap();
+    assert_eq!(pool.balance(&staker.tokens), 500_000);
+    assert_eq!(pool.stake_account(&staker).unstaking_amount, 0);
+    assert_eq!(pool.withdraw(&staker), error_code(ErrorCode::NothingToWithdraw));
+}
+
+#[test]
+fn locked_stake_cannot_be_unstaked_until_the_lockup_ends() {
+    let mut pool = Pool::new(Pubkey::new_unique(), 0);
+    let staker = pool.staker(1_500_000);
+
+    set_clock(0);
+    pool.stake(&staker, 1_000_000, LockupTerm::Days90).unwrap();
+    let lockup_end = pool.stake_account(&staker).lockup_end;
+    assert_eq!(lockup_end, 90 * SECONDS_PER_DAY);
+
+    // Staking more under a shorter term keeps the longer lockup over the whole position
+    set_clock(30 * SECONDS_PER_DAY);
+    pool.stake(&staker, 500_000, LockupTerm::Days30).unwrap();
+    assert_eq!(pool.stake_account(&staker).lockup_end, lockup_end);
+    assert_eq!(
+        pool.request_unstake(&staker, 1),
+        error_code(ErrorCode::StakeLocked)
+    );
+
+    set_clock(lockup_end - 1);
+    assert_eq!(
+        pool.request_unstake(&staker, 1_000_000),
+        error_code(ErrorCode::StakeLocked)
+    );
+
+    set_clock(lockup_end);
+    pool.request_unstake(&staker, 1_500_000).unwrap();
+    assert_eq!(pool.stake_account(&staker).unstaking_amount, 1_500_000);
+}
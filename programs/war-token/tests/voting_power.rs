@@ -0,0 +1,148 @@
+mod common;
+
+use anchor_lang::{prelude::*, solana_program::entrypoint::ProgramResult, system_program};
+use common::{error_code, set_clock, Pool, Staker, TestAccount};
+use war_token::{
+    instruction, ErrorCode, LockupTerm, Proposal, VoteType, DEFAULT_UNSTAKE_COOLDOWN,
+    MAX_STAKE_SNAPSHOTS,
+};
+
+const PROPOSAL_SPACE: usize = 8 + 8 + 32 + 4 + 64 + 4 + 256 + 8 + 8 + 8 + 8 + 1 + 1;
+const CREATED_AT: i64 = 1_000;
+
+fn proposal(pool: &mut Pool, created_at: i64) -> Pubkey {
+    let key = Pubkey::new_unique();
+    let proposal = Proposal {
+        number: 0,
+        proposer: Pubkey::new_unique(),
+        title: "Raise the fee discount".to_string(),
+        description: String::new(),
+        created_at,
+        voting_ends_at: created_at + DEFAULT_UNSTAKE_COOLDOWN,
+        votes_for: 0,
+        votes_against: 0,
+        executed: false,
+        bump: 0,
+    };
+    pool.insert(TestAccount::program_owned(key, &proposal, PROPOSAL_SPACE));
+    key
+}
+
+fn vote(pool: &mut Pool, proposal: Pubkey, staker: &Staker) -> ProgramResult {
+    let vote_record = Pubkey::find_program_address(
+        &[b"vote", proposal.as_ref(), staker.wallet.as_ref()],
+        &war_token::ID,
+    )
+    .0;
+    pool.insert(TestAccount::uninitialized(vote_record, 8 + 32 + 32 + 1 + 8 + 1));
+    let keys = [
+        vote_record,
+        proposal,
+        staker.stake,
+        staker.wallet,
+        system_program::ID,
+    ];
+    pool.run(
+        instruction::VoteOnProposal {
+            vote_type: VoteType::For,
+        },
+        &keys,
+    )
+}
+
+#[test]
+fn stake_added_after_creation_does_not_vote() {
+    let mut pool = Pool::new(Pubkey::new_unique(), 0);
+    let early = pool.staker(3_000_000);
+    let late = pool.staker(1_000_000);
+    let proposal = proposal(&mut pool, CREATED_AT);
+
+    set_clock(CREATED_AT - 1);
+    pool.stake(&early, 1_000_000, LockupTerm::None).unwrap();
+
+    // Staked in the same second the proposal was created: too late
+    set_clock(CREATED_AT);
+    pool.stake(&early, 2_000_000, LockupTerm::None).unwrap();
+    pool.stake(&late, 1_000_000, LockupTerm::None).unwrap();
+
+    set_clock(CREATED_AT + 10);
+    assert_eq!(pool.stake_account(&late).voting_power_at(CREATED_AT).unwrap(), 0);
+    assert_eq!(vote(&mut pool, proposal, &late), error_code(ErrorCode::NoVotingPower));
+    vote(&mut pool, proposal, &early).unwrap();
+    assert_eq!(pool.state::<Proposal>(&proposal).votes_for, 1_000_000);
+}
+
+#[test]
+fn unstaking_after_creation_keeps_earlier_power() {
+    let mut pool = Pool::new(Pubkey::new_unique(), 0);
+    let staker = pool.staker(1_000_000);
+
+    // Locked for 90 days, so it votes at 1.25x on a proposal made in the last second
+    set_clock(0);
+    pool.stake(&staker, 1_000_000, LockupTerm::Days90).unwrap();
+    let lockup_end = pool.stake_account(&staker).lockup_end;
+    let proposal = proposal(&mut pool, lockup_end - 1);
+
+    set_clock(lockup_end);
+    pool.request_unstake(&staker, 1_000_000).unwrap();
+
+    let stake_account = pool.stake_account(&staker);
+    assert_eq!(stake_account.staked_amount, 0);
+    assert_eq!(stake_account.voting_power_at(lockup_end - 1).unwrap(), 1_250_000);
+    assert_eq!(stake_account.voting_power_at(lockup_end + 1).unwrap(), 0);
+
+    set_clock(lockup_end + 1);
+    vote(&mut pool, proposal, &staker).unwrap();
+    assert_eq!(pool.state::<Proposal>(&proposal).votes_for, 1_250_000);
+}
+
+#[test]
+fn pruned_history_refuses_to_vote_rather_than_reporting_no_power() {
+    let mut pool = Pool::new(Pubkey::new_unique(), 0);
+    let staker = pool.staker(1_000_000);
+    let old_proposal = proposal(&mut pool, 2);
+    let recent_proposal = proposal(&mut pool, 3);
+
+    // One stake per second, one more than the history holds
+    for now in 1..=MAX_STAKE_SNAPSHOTS as i64 + 1 {
+        set_clock(now);
+        pool.stake(&staker, 1, LockupTerm::None).unwrap();
+    }
+
+    let stake_account = pool.stake_account(&staker);
+    assert_eq!(stake_account.snapshots.len(), MAX_STAKE_SNAPSHOTS);
+    assert_eq!(stake_account.snapshots[0].timestamp, 2);
+    assert_eq!(stake_account.pruned_until, Some(2));
+
+    // The stake held before t=2 went with the first snapshot: it was 1, not 0
+    assert!(stake_account.voting_power_at(2).is_err());
+    assert_eq!(stake_account.voting_power_at(3).unwrap(), 2);
+    assert_eq!(
+        stake_account.voting_power_at(i64::MAX).unwrap(),
+        MAX_STAKE_SNAPSHOTS as u64 + 1
+    );
+
+    set_clock(MAX_STAKE_SNAPSHOTS as i64 + 2);
+    assert_eq!(
+        vote(&mut pool, old_proposal, &staker),
+        error_code(ErrorCode::StakeHistoryPruned)
+    );
+    vote(&mut pool, recent_proposal, &staker).unwrap();
+    assert_eq!(pool.state::<Proposal>(&recent_proposal).votes_for, 2);
+}
+
+#[test]
+fn history_within_the_cap_is_never_pruned() {
+    let mut pool = Pool::new(Pubkey::new_unique(), 0);
+    let staker = pool.staker(1_000_000);
+
+    for now in 1..=MAX_STAKE_SNAPSHOTS as i64 {
+        set_clock(now);
+        pool.stake(&staker, 1, LockupTerm::None).unwrap();
+    }
+
+    // Before the first stake there was genuinely nothing staked
+    let stake_account = pool.stake_account(&staker);
+    assert_eq!(stake_account.pruned_until, None);
+    assert_eq!(stake_account.voting_power_at(1).unwrap(), 0);
+}